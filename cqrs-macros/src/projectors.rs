@@ -159,6 +159,19 @@ impl<'a> From<Projector<'a>> for TokenStream {
         let store = value.store().ident();
         let id = value.id();
         let event = &value.events;
+        let (mutability, restrict) = if event.is_empty() {
+            (quote! {}, quote! {})
+        } else {
+            // only read the events that are received unless the filter specifies the types
+            (
+                quote! { mut },
+                quote! {
+                    if filter.is_none_or(|filter| filter.types().is_empty()) {
+                        builder = builder #(.add_type(#event::schema()))*;
+                    }
+                },
+            )
+        };
 
         quote! {
             impl #impl_generics cqrs::projection::Projector<#id, #output> for #struct_ #ty_generics #where_ {
@@ -199,20 +212,14 @@ impl<'a> From<Projector<'a>> for TokenStream {
                         let __ret: Result<#output, Box<dyn std::error::Error + Send>> = {
                             use cqrs::message::Encoded;
                             use futures::StreamExt;
-                            let predicate = if let Some(filter) = filter {
-                                let builder: cqrs::event::PredicateBuilder<_> = filter.into();
-                                builder
-                                    #(.add_type(#event::schema()))*
-                                    .build()
-                            } else {
-                                cqrs::event::Predicate::default()
-                            };
-                            let predicate = if filter.is_some() {
-                                Some(&predicate)
+                            let #mutability builder: cqrs::event::PredicateBuilder<_> = if let Some(filter) = filter {
+                                filter.into()
                             } else {
-                                None
+                                cqrs::event::PredicateBuilder::new(None)
                             };
-                            let mut stream = __self.#store.load(predicate).await;
+                            #restrict
+                            let predicate = builder.build();
+                            let mut stream = __self.#store.load(Some(&predicate)).await;
                             while let Some(result) = stream.next().await {
                                 let saved = result
                                     .map_err(|e| {
@@ -4,7 +4,7 @@ use aws_config::SdkConfig;
 use aws_sdk_dynamodb::Client;
 use cqrs::{
     Clock, Concurrency, Mask, WallClock,
-    event::{Delete, Event, StoreOptions as EventStoreOptions},
+    event::{DecodePolicy, Delete, Event, StoreOptions as EventStoreOptions},
     message::{Message, Transcoder},
     snapshot::{Snapshot, StoreOptions as SnapshotStoreOptions},
 };
@@ -29,6 +29,7 @@ pub struct Builder<ID, M: Message + ?Sized> {
     client: Option<Client>,
     concurrency: Concurrency,
     delete: Delete,
+    decode: DecodePolicy,
     mask: Option<Arc<dyn Mask>>,
     clock: Option<Arc<dyn Clock>>,
     transcoder: Option<Arc<Transcoder<M>>>,
@@ -43,6 +44,7 @@ impl<ID, M: Message + ?Sized> Default for Builder<ID, M> {
             client: Default::default(),
             concurrency: Default::default(),
             delete: Default::default(),
+            decode: Default::default(),
             mask: Default::default(),
             clock: Default::default(),
             transcoder: Default::default(),
@@ -155,6 +157,16 @@ impl<ID> Builder<ID, dyn Event> {
         self
     }
 
+    /// Configures the behavior when a stored event cannot be decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the [decode policy](DecodePolicy) to apply
+    pub fn decode_policy(mut self, value: DecodePolicy) -> Self {
+        self.decode = value;
+        self
+    }
+
     /// Configures the snapshots associated with the store.
     ///
     /// # Arguments
@@ -169,7 +181,7 @@ impl<ID> Builder<ID, dyn Event> {
     pub fn build(mut self) -> Result<EventStore<ID>, BuilderError> {
         let client = self.resolve_client()?;
         let table = format!("{}_Events", self.table.ok_or(MissingTable)?);
-        let options = EventStoreOptions::<ID>::new(
+        let mut options = EventStoreOptions::<ID>::new(
             self.concurrency,
             self.delete,
             self.mask,
@@ -178,6 +190,8 @@ impl<ID> Builder<ID, dyn Event> {
             self.snapshots,
         );
 
+        options.set_decode_policy(self.decode);
+
         Ok(EventStore::new(client, table, options))
     }
 }
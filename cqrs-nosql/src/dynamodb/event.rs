@@ -15,17 +15,20 @@ use aws_sdk_dynamodb::{
     primitives::Blob,
     types::{
         AttributeValue::{self as Attr, B, N, S},
-        Put, TransactWriteItem, Update,
+        DeleteRequest, Put, Select, TransactWriteItem, Update, WriteRequest,
     },
 };
 use cqrs::{
-    Clock, Mask, Range, Version,
+    Clock, Concurrency, Mask, Range, Version,
     event::{
-        Event, EventStream, IdStream, Predicate, PredicateBuilder, Store, StoreError, StoreOptions,
+        Append, Event, EventStream, ExpectedVersion, Head, IdPage, IdStream, Predicate,
+        PredicateBuilder, RawEvent, RawEventStream, Store, StoreError, StoreOptions,
     },
-    message::{Saved, Schema},
+    message::{Descriptor, Saved, Schema},
+};
+use std::{
+    collections::HashMap, error::Error, fmt::Debug, str::FromStr, sync::Arc, time::SystemTime,
 };
-use std::{error::Error, fmt::Debug, str::FromStr, sync::Arc, time::SystemTime};
 
 fn apply_predicate<T>(
     mut request: QueryFluentBuilder,
@@ -75,6 +78,15 @@ where
             request = request.expression_attribute_values(":to", N(crate::to_secs(to).to_string()));
         }
 
+        if let Some(cid) = predicate.correlation_id {
+            if !filter.is_empty() {
+                filter.push_str(" AND ");
+            }
+
+            filter.push_str("(correlationId = :cid)");
+            request = request.expression_attribute_values(":cid", S(cid.into()));
+        }
+
         let mut schemas = predicate.types.iter();
 
         if let Some(schema) = schemas.next() {
@@ -125,12 +137,50 @@ where
             request = request.filter_expression(filter);
         }
 
-        request.key_condition_expression(condition)
+        if predicate.direction.backward() {
+            request = request.scan_index_forward(false);
+        }
+
+        if let Some(limit) = predicate.limit {
+            // the limit is only the page size; the caller stops reading once the limit is reached
+            request = request.limit(limit.clamp(1, i32::MAX as usize) as i32);
+        }
+
+        if let Some(token) = predicate.after {
+            let mut version = token.version();
+
+            if let Some(mask) = mask {
+                version = version.unmask(mask);
+            }
+
+            let id = predicate.id.unwrap_or(token.id());
+
+            request = request
+                .exclusive_start_key("id", S(id.to_string()))
+                .exclusive_start_key("version", N(version.sort_key().to_string()));
+        }
+
+        // a query across identifiers provides its own key condition
+        if condition.is_empty() {
+            request
+        } else {
+            request.key_condition_expression(condition)
+        }
     } else {
         request
     }
 }
 
+// REMARKS: DynamoDB cannot enforce uniqueness on a non-key attribute. the message identifier is
+// instead recorded as a marker item in its own partition, which is written in the same transaction
+// as the events. the marker uses version 0, which is never used by an event. the first event of
+// the batch also records the message identifier so the marker can be found when the stream is
+// deleted without scanning the table.
+#[inline]
+fn message_key<T: ToString>(id: &T, message_id: &str) -> String {
+    format!("{}#message#{}", id.to_string(), message_id)
+}
+
 /// Represents an Amazon DynamoDB [event store](Store).
 pub struct EventStore<ID> {
     ddb: Client,
@@ -281,6 +331,7 @@ where
         id: &ID,
         mut version: Version,
         events: &[Box<dyn Event>],
+        message_id: Option<&str>,
     ) -> Result<Version, StoreError<ID>> {
         let stored_on = crate::to_secs(self.options.clock().now());
         let mut request = self.ddb.transact_write_items();
@@ -307,7 +358,7 @@ where
 
         let mut current_version = version;
 
-        for event in events {
+        for (index, event) in events.iter().enumerate() {
             let schema = event.schema();
             let content = self.options.transcoder().encode(event.as_ref())?;
             let mut put = Put::builder()
@@ -324,6 +375,12 @@ where
                 put = put.item("correlationId", S(cid.into()));
             }
 
+            if index == 0
+                && let Some(message_id) = message_id
+            {
+                put = put.item("messageId", S(message_id.into()));
+            }
+
             request = request.transact_items(
                 TransactWriteItem::builder()
                     .put(put.build().unwrap())
@@ -335,12 +392,35 @@ where
 
         version = current_version;
 
+        if let Some(message_id) = message_id {
+            let put = Put::builder()
+                .table_name(&self.table)
+                .item("id", S(message_key(id, message_id)))
+                .item("version", N("0".into()))
+                .item("messageVersion", N(version.sort_key().to_string()))
+                .condition_expression("attribute_not_exists(id)");
+
+            request = request.transact_items(
+                TransactWriteItem::builder()
+                    .put(put.build().unwrap())
+                    .build(),
+            );
+        }
+
         if let Err(failure) = request.send().await {
             let error = failure.into_service_error();
 
             if let TransactionCanceledException(canceled) = &error
                 && let Some(reasons) = &canceled.cancellation_reasons
             {
+                // the message marker is always the last item
+                if message_id.is_some()
+                    && let Some(reason) = reasons.last()
+                    && reason.code.as_deref() == Some("ConditionalCheckFailed")
+                {
+                    return Err(StoreError::Conflict(id.clone(), version.number()));
+                }
+
                 for reason in reasons {
                     if let Some(code) = &reason.code
                         && code == "ConditionalCheckFailed"
@@ -363,6 +443,320 @@ where
             Ok(version)
         }
     }
+
+    async fn delete_markers(&self, id: &ID) -> Result<(), StoreError<ID>> {
+        const MAX_BATCH_SIZE: usize = 25;
+
+        let query = self
+            .ddb
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("id = :id")
+            .filter_expression("attribute_exists(messageId)")
+            .expression_attribute_values(":id", S(id.to_string()))
+            .projection_expression("messageId")
+            .into_paginator();
+        let mut items = query.items().send();
+        let mut batch = Vec::with_capacity(MAX_BATCH_SIZE);
+
+        while let Some(item) = items.next().await {
+            let item = item.box_err()?;
+            let message_id: String = coerce("messageId", &item, Attr::as_s);
+
+            batch.push(
+                WriteRequest::builder()
+                    .delete_request(
+                        DeleteRequest::builder()
+                            .key("id", S(message_key(id, &message_id)))
+                            .key("version", N("0".into()))
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            );
+
+            if batch.len() == MAX_BATCH_SIZE {
+                self.ddb
+                    .batch_write_item()
+                    .request_items(&self.table, batch)
+                    .send()
+                    .await
+                    .box_err()?;
+                batch = Vec::with_capacity(MAX_BATCH_SIZE);
+            }
+        }
+
+        if !batch.is_empty() {
+            self.ddb
+                .batch_write_item()
+                .request_items(&self.table, batch)
+                .send()
+                .await
+                .box_err()?;
+        }
+
+        Ok(())
+    }
+
+    async fn current_version(&self, id: &ID) -> Result<Option<Version>, StoreError<ID>> {
+        let output = self
+            .ddb
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("id = :id")
+            .expression_attribute_values(":id", S(id.to_string()))
+            .projection_expression("version")
+            .scan_index_forward(false)
+            .consistent_read(true)
+            .limit(1)
+            .send()
+            .await
+            .box_err()?;
+
+        Ok(output
+            .items()
+            .first()
+            .map(|item| from_sort_key(coerce("version", item, Attr::as_n))))
+    }
+
+    async fn write_appends(
+        &self,
+        appends: &[Append<'_, ID>],
+    ) -> Result<Vec<Version>, StoreError<ID>> {
+        // each transaction item is tracked so that a failed condition can be mapped to an error
+        enum Check {
+            Deleted(usize),
+            Conflict(usize, u32),
+        }
+
+        let stored_on = crate::to_secs(self.options.clock().now());
+        let mut request = self.ddb.transact_write_items();
+        let mut checks = Vec::new();
+        let mut versions = Vec::with_capacity(appends.len());
+        let mut batched = HashMap::<String, Version>::new();
+
+        for (index, append) in appends.iter().enumerate() {
+            let id = append.id();
+            let key = id.to_string();
+
+            if append.events().is_empty() {
+                versions.push(append.expected_version());
+                continue;
+            }
+
+            let mut version = if append.expected_version() != Version::default()
+                && let Some(mask) = self.options.mask()
+            {
+                append.expected_version().unmask(mask)
+            } else {
+                append.expected_version()
+            };
+
+            if version.invalid() {
+                return Err(StoreError::InvalidVersion);
+            }
+
+            if !self.options.concurrency().enforced() {
+                let current = if let Some(current) = batched.get(&key) {
+                    Some(*current)
+                } else {
+                    self.current_version(id).await?
+                };
+
+                if let Some(current) = current
+                    && current.number() > version.number()
+                {
+                    version = new_version(current.number(), 0);
+                }
+            }
+
+            version = version.increment(ByOne);
+
+            if self.options.delete().supported()
+                && let Some(previous) = version.previous()
+            {
+                // the following update doesn't change anything, but it ensures the previous
+                // version still exists and hasn't been deleted
+                let update = Update::builder()
+                    .table_name(&self.table)
+                    .key("id", S(key.clone()))
+                    .key("version", N(previous.sort_key().to_string()))
+                    .update_expression("SET version = :version")
+                    .condition_expression("attribute_exists(id) AND attribute_exists(version)")
+                    .expression_attribute_values(":version", N(previous.sort_key().to_string()));
+
+                request = request.transact_items(
+                    TransactWriteItem::builder()
+                        .update(update.build().unwrap())
+                        .build(),
+                );
+                checks.push(Check::Deleted(index));
+            }
+
+            let mut current_version = version;
+
+            for event in append.events() {
+                let schema = event.schema();
+                let content = self.options.transcoder().encode(event.as_ref())?;
+                let mut put = Put::builder()
+                    .table_name(&self.table)
+                    .item("id", S(key.clone()))
+                    .item("version", N(version.sort_key().to_string()))
+                    .item("storedOn", N(stored_on.to_string()))
+                    .item("kind", S(schema.kind().into()))
+                    .item("revision", N(schema.version().to_string()))
+                    .item("content", B(Blob::new(content)))
+                    .condition_expression(
+                        "attribute_not_exists(id) AND attribute_not_exists(version)",
+                    );
+
+                if let Some(cid) = event.correlation_id() {
+                    put = put.item("correlationId", S(cid.into()));
+                }
+
+                request = request.transact_items(
+                    TransactWriteItem::builder()
+                        .put(put.build().unwrap())
+                        .build(),
+                );
+                checks.push(Check::Conflict(index, version.number()));
+                current_version = version;
+                version = current_version.increment(Sequence);
+            }
+
+            batched.insert(key, current_version);
+
+            if let Some(mask) = self.options.mask() {
+                current_version = current_version.mask(mask);
+            }
+
+            versions.push(current_version);
+        }
+
+        if checks.is_empty() {
+            return Ok(versions);
+        }
+
+        if let Err(failure) = request.send().await {
+            let error = failure.into_service_error();
+
+            if let TransactionCanceledException(canceled) = &error
+                && let Some(reasons) = &canceled.cancellation_reasons
+            {
+                for (reason, check) in reasons.iter().zip(&checks) {
+                    if reason.code.as_deref() == Some("ConditionalCheckFailed") {
+                        return Err(match check {
+                            Check::Deleted(index) => {
+                                StoreError::Deleted(appends[*index].id().clone())
+                            }
+                            Check::Conflict(index, version) => {
+                                StoreError::Conflict(appends[*index].id().clone(), *version)
+                            }
+                        });
+                    }
+                }
+            }
+
+            Err(StoreError::Unknown(Box::new(error) as Box<dyn Error + Send>))
+        } else {
+            Ok(versions)
+        }
+    }
+
+    async fn find(
+        &self,
+        id: &ID,
+        message_id: Option<&str>,
+    ) -> Result<Option<Version>, StoreError<ID>> {
+        if let Some(message_id) = message_id {
+            let output = self
+                .ddb
+                .get_item()
+                .table_name(&self.table)
+                .key("id", S(message_key(id, message_id)))
+                .key("version", N("0".into()))
+                .consistent_read(true)
+                .send()
+                .await
+                .box_err()?;
+
+            if let Some(item) = output.item() {
+                let mut version = from_sort_key(coerce("messageVersion", item, Attr::as_n));
+
+                if let Some(mask) = self.options.mask() {
+                    version = version.mask(mask);
+                }
+
+                return Ok(Some(version));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn append(
+        &self,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: Option<&str>,
+        concurrency: Concurrency,
+    ) -> Result<Version, StoreError<ID>> {
+        if events.is_empty() {
+            return Ok(expected_version);
+        }
+
+        if let Some(version) = self.find(id, message_id).await? {
+            return Ok(version);
+        }
+
+        let mut version = if expected_version != Version::default()
+            && let Some(mask) = self.options.mask()
+        {
+            expected_version.unmask(mask)
+        } else {
+            expected_version
+        };
+
+        if version.invalid() {
+            return Err(StoreError::InvalidVersion);
+        }
+
+        loop {
+            version = version.increment(ByOne);
+
+            let result = if events.len() == 1 && message_id.is_none() {
+                self.write_one(id, version, &events[0]).await
+            } else {
+                self.write_all(id, version, events, message_id).await
+            };
+
+            match result {
+                Ok(current) => {
+                    version = current;
+                    break;
+                }
+                Err(error) => {
+                    if matches!(error, StoreError::Conflict(_, _)) {
+                        // the message may have been saved concurrently
+                        if let Some(version) = self.find(id, message_id).await? {
+                            return Ok(version);
+                        } else if !concurrency.enforced() {
+                            continue;
+                        }
+                    }
+
+                    return Err(error);
+                }
+            }
+        }
+
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
+        }
+
+        Ok(version)
+    }
 }
 
 #[async_trait]
@@ -396,14 +790,56 @@ where
         })
     }
 
+    async fn ids_page(
+        &self,
+        stored_on: Range<SystemTime>,
+        limit: usize,
+        after: Option<&T>,
+    ) -> Result<IdPage<T>, StoreError<T>> {
+        let first = N(new_version(1, 0).sort_key().to_string());
+        let mut request = self
+            .ddb
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("version = :version")
+            .expression_attribute_values(":version", first.clone())
+            .limit(limit.clamp(1, i32::MAX as usize) as i32);
+
+        if let Some(id) = after {
+            request = request
+                .exclusive_start_key("id", S(id.to_string()))
+                .exclusive_start_key("version", first);
+        }
+
+        let predicate = PredicateBuilder::<T>::new(None)
+            .stored_on(stored_on)
+            .build();
+        let query =
+            apply_predicate(request, Some(&predicate), self.options.mask()).into_paginator();
+        let mut items = query.items().send();
+        let mut ids = Vec::with_capacity(limit);
+
+        // a filtered page can have fewer items than the limit so keep reading until it is full
+        while ids.len() < limit
+            && let Some(item) = items.next().await
+        {
+            ids.push(coerce::<T>("id", &item.box_err()?, Attr::as_s));
+        }
+
+        Ok(IdPage::of(ids, limit))
+    }
+
     async fn load<'a>(&self, predicate: Option<&'a Predicate<'a, T>>) -> EventStream<'a, T> {
         let request = self.ddb.query().table_name(&self.table);
         let query = apply_predicate(request, predicate, self.options.mask()).into_paginator();
         let mut items = query.items().send();
         let options = self.options.clone();
+        let mut remaining = predicate.and_then(|p| p.limit).unwrap_or(usize::MAX);
 
         Box::pin(try_stream! {
-            while let Some(item) = items.next().await {
+            while remaining > 0 && let Some(item) = items.next().await {
+                remaining -= 1;
+
                 let attributes = item.box_err()?;
                 let mut version = from_sort_key(coerce("version", &attributes, Attr::as_n));
                 let schema = Schema::new(
@@ -415,70 +851,188 @@ where
                 } else {
                     &empty
                 };
-                let event = options.transcoder().decode(&schema, content.as_ref())?;
 
                 if let Some(mask) = options.mask() {
                     version = version.mask(mask);
                 }
 
-                yield Saved::new(event, version);
+                if let Some(event) = options.decode(&schema, version, content.as_ref())? {
+                    yield Saved::new(event, version);
+                }
             }
         })
     }
 
+    async fn load_raw<'a>(&self, predicate: Option<&'a Predicate<'a, T>>) -> RawEventStream<'a, T> {
+        let request = self.ddb.query().table_name(&self.table);
+        let query = apply_predicate(request, predicate, self.options.mask()).into_paginator();
+        let mut items = query.items().send();
+        let options = self.options.clone();
+        let mut remaining = predicate.and_then(|p| p.limit).unwrap_or(usize::MAX);
+
+        Box::pin(try_stream! {
+            while remaining > 0 && let Some(item) = items.next().await {
+                remaining -= 1;
+
+                let attributes = item.box_err()?;
+                let id = coerce::<T>("id", &attributes, Attr::as_s);
+                let stored_on = crate::from_secs(coerce("storedOn", &attributes, Attr::as_n));
+                let mut version = from_sort_key(coerce("version", &attributes, Attr::as_n));
+                let schema = Schema::new(
+                    coerce::<String>("kind", &attributes, Attr::as_s),
+                    coerce("revision", &attributes, Attr::as_n));
+                let content = if let Some(attribute) = attributes.get("content") {
+                    attribute.as_b().map(|blob| blob.as_ref().to_vec()).unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+
+                if let Some(mask) = options.mask() {
+                    version = version.mask(mask);
+                }
+
+                yield RawEvent::new(id, stored_on, Descriptor::new(schema, version, content));
+            }
+        })
+    }
+
+    async fn head(&self, id: &T) -> Result<Head<T>, StoreError<T>> {
+        let key = id.to_string();
+        let last = self
+            .ddb
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("id = :id")
+            .expression_attribute_values(":id", S(key.clone()))
+            .projection_expression("version, storedOn")
+            .scan_index_forward(false)
+            .consistent_read(true)
+            .limit(1)
+            .send()
+            .await
+            .box_err()?;
+        let Some(last) = last.items().first() else {
+            return Ok(Head::empty(id.clone()));
+        };
+        let first = self
+            .ddb
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("id = :id")
+            .expression_attribute_values(":id", S(key.clone()))
+            .projection_expression("storedOn")
+            .consistent_read(true)
+            .limit(1)
+            .send()
+            .await
+            .box_err()?;
+        let first = first.items().first().unwrap_or(last);
+
+        // only keys are read to count the items, but it may span multiple pages
+        let mut pages = self
+            .ddb
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("id = :id")
+            .expression_attribute_values(":id", S(key))
+            .select(Select::Count)
+            .consistent_read(true)
+            .into_paginator()
+            .send();
+        let mut count = 0;
+
+        while let Some(page) = pages.next().await {
+            count += page.box_err()?.count() as u64;
+        }
+
+        let mut version = from_sort_key(coerce("version", last, Attr::as_n));
+
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
+        }
+
+        Ok(Head::new(
+            id.clone(),
+            version,
+            count,
+            crate::from_secs(coerce("storedOn", first, Attr::as_n)),
+            crate::from_secs(coerce("storedOn", last, Attr::as_n)),
+        ))
+    }
+
     async fn save(
         &self,
         id: &T,
         expected_version: Version,
         events: &[Box<dyn Event>],
     ) -> Result<Version, StoreError<T>> {
-        if events.is_empty() {
-            return Ok(expected_version);
-        }
+        let concurrency = self.options.concurrency();
+        self.append(id, expected_version, events, None, concurrency)
+            .await
+    }
 
-        let mut version = if expected_version != Version::default()
-            && let Some(mask) = self.options.mask()
-        {
-            expected_version.unmask(mask)
-        } else {
-            expected_version
-        };
+    async fn save_expecting(
+        &self,
+        id: &T,
+        expected: ExpectedVersion,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<T>> {
+        let mut current = self.current_version(id).await?;
 
-        if version.invalid() {
-            return Err(StoreError::InvalidVersion);
+        if let Some(mask) = self.options.mask() {
+            current = current.map(|version| version.mask(mask));
         }
 
-        loop {
-            version = version.increment(ByOne);
-
-            let result = if events.len() == 1 {
-                self.write_one(id, version, &events[0]).await
-            } else {
-                self.write_all(id, version, events).await
-            };
+        // versions only increase and each is written at most once so the events cannot be
+        // appended at the expected version if the stream moves after it is read
+        let expected_version = match expected {
+            ExpectedVersion::Any => current.unwrap_or_default(),
+            ExpectedVersion::NoStream => Version::default(),
+            ExpectedVersion::StreamExists => {
+                current.ok_or_else(|| StoreError::NotFound(id.clone()))?
+            }
+            ExpectedVersion::Exact(version) => match current {
+                Some(current) if current == version => version,
+                Some(_) => {
+                    let version = if let Some(mask) = self.options.mask() {
+                        version.unmask(mask)
+                    } else {
+                        version
+                    };
 
-            match result {
-                Ok(current) => {
-                    version = current;
-                    break;
+                    return Err(StoreError::Conflict(id.clone(), version.number()));
                 }
-                Err(error) => {
-                    if matches!(error, StoreError::Conflict(_, _))
-                        && !self.options.concurrency().enforced()
-                    {
-                        continue;
-                    } else {
-                        return Err(error);
-                    }
+                None => return Err(StoreError::NotFound(id.clone())),
+            },
+        };
+
+        self.append(id, expected_version, events, None, expected.concurrency())
+            .await
+    }
+
+    async fn save_once(
+        &self,
+        id: &T,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: &str,
+    ) -> Result<Version, StoreError<T>> {
+        let concurrency = self.options.concurrency();
+        self.append(id, expected_version, events, Some(message_id), concurrency)
+            .await
+    }
+
+    async fn save_all(&self, appends: &[Append<'_, T>]) -> Result<Vec<Version>, StoreError<T>> {
+        // REMARKS: a transaction is limited to 100 items, which includes every event and, when
+        // deletes are supported, one additional item per append
+        loop {
+            match self.write_appends(appends).await {
+                Err(StoreError::Conflict(_, _)) if !self.options.concurrency().enforced() => {
+                    continue;
                 }
+                result => return result,
             }
         }
-
-        if let Some(mask) = self.options.mask() {
-            version = version.mask(mask);
-        }
-
-        Ok(version)
     }
 
     async fn delete(&self, id: &T) -> Result<(), StoreError<T>> {
@@ -490,6 +1044,9 @@ where
             snapshots.prune(id, None).await?;
         }
 
+        // markers are deleted first so that a failed delete can be retried while the events that
+        // reference them still exist
+        self.delete_markers(id).await?;
         delete_all(&self.ddb, &self.table, id.to_string(), None).await?;
         Ok(())
     }
@@ -8,7 +8,7 @@ pub mod dynamodb;
 
 use std::{
     error::Error,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 #[allow(dead_code)]
@@ -25,4 +25,9 @@ impl<T, E: Error + Send + 'static> BoxErr<T> for Result<T, E> {
 #[allow(dead_code)]
 pub(crate) fn to_secs(timestamp: SystemTime) -> u64 {
     timestamp.duration_since(UNIX_EPOCH).unwrap().as_secs()
-}
\ No newline at end of file
+}
+
+#[allow(dead_code)]
+pub(crate) fn from_secs(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
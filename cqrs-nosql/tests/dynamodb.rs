@@ -0,0 +1,119 @@
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_dynamodb::{Client, config::Credentials};
+use cqrs::{
+    Aggregate, Repository, StoreMigration, aggregate, event, event::ExpectedVersion, transcode,
+    when,
+};
+use cqrs_nosql::dynamodb::{EventStore, EventStoreMigration};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, sync::Arc};
+use testcontainers_modules::{dynamodb_local::DynamoDb, testcontainers::runners::AsyncRunner};
+
+type TestResult = Result<(), Box<dyn Error + 'static>>;
+
+#[transcode(with = Json)]
+mod events {
+    #[event]
+    #[derive(Default, Deserialize, Serialize)]
+    pub struct Opened {
+        pub id: String,
+        pub amount: f32,
+    }
+
+    #[event]
+    #[derive(Default, Deserialize, Serialize)]
+    pub struct Credited {
+        pub id: String,
+        pub amount: f32,
+    }
+}
+
+#[aggregate(String)]
+#[derive(Default)]
+struct Account {
+    balance: f32,
+}
+
+#[aggregate(String)]
+impl Account {
+    fn open(id: &str, amount: f32) -> Self {
+        let mut me = Self::default();
+
+        me.record(Opened {
+            id: id.into(),
+            amount,
+        });
+        me
+    }
+
+    fn credit(&mut self, amount: f32) {
+        self.record(Credited {
+            id: self.id.clone(),
+            amount,
+        });
+    }
+
+    #[when]
+    fn opened(&mut self, event: &Opened) {
+        self.id = event.id.clone();
+        self.balance += event.amount;
+    }
+
+    #[when]
+    fn credited(&mut self, event: &Credited) {
+        self.balance += event.amount;
+    }
+}
+
+async fn new_client(port: u16) -> Client {
+    let credentials = Credentials::new("fakeKey", "fakeSecret", None, None, "test");
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .region(Region::new("us-west-2"))
+        .endpoint_url(format!("http://127.0.0.1:{}", port))
+        .credentials_provider(credentials)
+        .load()
+        .await;
+
+    Client::new(&config)
+}
+
+#[tokio::test]
+async fn verify_dynamodb_saves_exact_version_after_multiple_events() -> TestResult {
+    // arrange
+    let dynamodb = DynamoDb::default().start().await?;
+    let port = dynamodb.get_host_port_ipv4(8000).await?;
+    let client = new_client(port).await;
+    let events = Arc::new(
+        EventStore::<String>::builder()
+            .client(client.clone())
+            .table("Accounts")
+            .transcoder(transcoder::events())
+            .build()?,
+    );
+
+    EventStoreMigration::new(client, "Accounts_Events")
+        .run()
+        .await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+
+    account.credit(25.0);
+    repository.save(&mut account).await?;
+
+    let version = account.version();
+
+    account.credit(10.0);
+
+    // act
+    repository
+        .save_expecting(&mut account, ExpectedVersion::Exact(version))
+        .await?;
+
+    // assert
+    let head = event::Store::head(&*events, &"12345".into()).await?;
+
+    assert_ne!(account.version(), version);
+    assert_eq!(head.version(), account.version());
+    Ok(())
+}
@@ -1,9 +1,12 @@
 use self::SqlStoreBuilderError::*;
-use crate::{event, snapshot, sql::Ident};
+use crate::{
+    event, snapshot,
+    sql::{Columns, ContentType, Ident, Partitioning},
+};
 use cfg_if::cfg_if;
 use cqrs::{
     Clock, Concurrency, Mask, WallClock,
-    event::{Delete, Event, StoreOptions as EventStoreOptions},
+    event::{DecodePolicy, Delete, Event, StoreOptions as EventStoreOptions},
     message::{Message, Transcoder},
     snapshot::{Snapshot, StoreOptions as SnapshotStoreOptions},
 };
@@ -37,8 +40,14 @@ where
 {
     schema: &'static str,
     table: Option<&'static str>,
+    pub(crate) columns: Columns,
+    pub(crate) content: ContentType,
+    pub(crate) notify: Option<&'static str>,
+    pub(crate) partitioning: Partitioning,
     concurrency: Concurrency,
     delete: Delete,
+    decode: DecodePolicy,
+    page_size: Option<usize>,
     pub(crate) url: Option<String>,
     pub(crate) options: Option<PoolOptions<DB>>,
     mask: Option<Arc<dyn Mask>>,
@@ -55,8 +64,14 @@ impl<ID, DB: Database> Default for SqlStoreBuilder<ID, dyn Event, DB> {
         Self {
             schema: "events",
             table: None,
+            columns: Default::default(),
+            content: Default::default(),
+            notify: None,
+            partitioning: Default::default(),
             concurrency: Default::default(),
             delete: Default::default(),
+            decode: Default::default(),
+            page_size: None,
             url: None,
             options: None,
             mask: None,
@@ -75,8 +90,14 @@ impl<ID, DB: Database> Default for SqlStoreBuilder<ID, dyn Snapshot, DB> {
         Self {
             schema: "snapshots",
             table: None,
+            columns: Default::default(),
+            content: Default::default(),
+            notify: None,
+            partitioning: Default::default(),
             concurrency: Concurrency::None,
             delete: Default::default(),
+            decode: Default::default(),
+            page_size: None,
             url: None,
             options: None,
             mask: None,
@@ -115,6 +136,21 @@ where
         self
     }
 
+    /// Configures the mapping of the columns in the table representing the store.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the table [columns](Columns)
+    ///
+    /// # Remarks
+    ///
+    /// The column mapping is typically only required when the store uses an existing table whose
+    /// columns do not match the default column names.
+    pub fn columns(mut self, value: Columns) -> Self {
+        self.columns = value;
+        self
+    }
+
     /// Configures the URL representing the database connection string.
     ///
     /// # Arguments
@@ -190,6 +226,16 @@ impl<ID, DB: Database> SqlStoreBuilder<ID, dyn Event, DB> {
         self
     }
 
+    /// Configures the behavior when a stored event cannot be decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the [decode policy](DecodePolicy) to apply
+    pub fn decode_policy(mut self, value: DecodePolicy) -> Self {
+        self.decode = value;
+        self
+    }
+
     /// Configures the snapshots associated with the store.
     ///
     /// # Arguments
@@ -200,6 +246,24 @@ impl<ID, DB: Database> SqlStoreBuilder<ID, dyn Event, DB> {
         self
     }
 
+    /// Configures the store to read events and identifiers in pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the maximum number of rows read in each page
+    ///
+    /// # Remarks
+    ///
+    /// By default, a stream holds a pooled connection until it is dropped, which can exhaust the
+    /// pool when a stream is consumed slowly. When a page size is configured, each page is read
+    /// using keyset pagination and the connection is returned to the pool before the page is
+    /// yielded. The rows of a page are held in memory, and the events are read in the order of
+    /// their identifier and version. Events loaded in a caller-owned transaction are not paged.
+    pub fn page_size(mut self, value: usize) -> Self {
+        self.page_size = Some(value);
+        self
+    }
+
     /// Builds and returns a new [event store](event::SqlStore).
     pub fn build(self) -> Result<event::SqlStore<ID, DB>, SqlStoreBuilderError> {
         let url = self.url.ok_or(MissingUrl)?;
@@ -210,7 +274,7 @@ impl<ID, DB: Database> SqlStoreBuilder<ID, dyn Event, DB> {
         } else {
             Ident::qualified(self.schema, table)
         };
-        let options = EventStoreOptions::<ID>::new(
+        let mut options = EventStoreOptions::<ID>::new(
             self.concurrency,
             self.delete,
             self.mask,
@@ -219,11 +283,16 @@ impl<ID, DB: Database> SqlStoreBuilder<ID, dyn Event, DB> {
             self.snapshots,
         );
 
-        Ok(event::SqlStore::new(
-            table,
-            pool.connect_lazy(&url)?,
-            options,
-        ))
+        options.set_decode_policy(self.decode);
+
+        let mut store = event::SqlStore::new(table, pool.connect_lazy(&url)?, options);
+
+        store.columns = self.columns.quote();
+        store.content = self.content;
+        store.notify = self.notify;
+        store.partitioning = self.partitioning;
+        store.page_size = self.page_size;
+        Ok(store)
     }
 }
 
@@ -244,11 +313,10 @@ impl<ID, DB: Database> SqlStoreBuilder<ID, dyn Snapshot, DB> {
             self.transcoder.unwrap_or_default(),
         );
 
-        Ok(snapshot::SqlStore::new(
-            table,
-            pool.connect_lazy(&url)?,
-            options,
-        ))
+        let mut store = snapshot::SqlStore::new(table, pool.connect_lazy(&url)?, options);
+
+        store.columns = self.columns.quote();
+        Ok(store)
     }
 }
 
@@ -274,7 +342,7 @@ cfg_if! {
                 } else {
                     format!("{}_{}", value.schema, table)
                 };
-                let options = EventStoreOptions::<ID>::new(
+                let mut options = EventStoreOptions::<ID>::new(
                     value.concurrency,
                     value.delete,
                     value.mask,
@@ -283,11 +351,13 @@ cfg_if! {
                     value.snapshots,
                 );
 
-                Ok(Self::new(
-                    table,
-                    pool,
-                    options,
-                ))
+                options.set_decode_policy(value.decode);
+
+                let mut store = Self::new(table, pool, options);
+
+                store.columns = value.columns.quote();
+                store.page_size = value.page_size;
+                Ok(store)
             }
         }
 
@@ -315,11 +385,71 @@ cfg_if! {
                     value.transcoder.unwrap_or_default()
                 );
 
-                Ok(Self::new(
-                    table,
-                    pool,
-                    options,
-                ))
+                let mut store = Self::new(table, pool, options);
+
+                store.columns = value.columns.quote();
+                Ok(store)
+            }
+        }
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "postgres")] {
+        use sqlx::Postgres;
+
+        impl<ID> SqlStoreBuilder<ID, dyn Event, Postgres> {
+            /// Configures the storage type of the event content.
+            ///
+            /// # Arguments
+            ///
+            /// * `value` - the storage [type](ContentType) of the event content
+            ///
+            /// # Remarks
+            ///
+            /// The default storage type is [binary](ContentType::Binary), which uses `BYTEA`.
+            /// Events encoded as JSON can be stored as [JSON](ContentType::Json), which uses `JSONB`
+            /// and allows the event content to be queried. The storage type must match the type of
+            /// the content column, which is created accordingly by the store migrations. An
+            /// existing table is not converted.
+            pub fn content_type(mut self, value: ContentType) -> Self {
+                self.content = value;
+                self
+            }
+
+            /// Configures the channel that is notified when events are saved.
+            ///
+            /// # Arguments
+            ///
+            /// * `channel` - the name of the notification channel
+            ///
+            /// # Remarks
+            ///
+            /// Each save issues a `NOTIFY` on the channel in the same transaction as the events,
+            /// which is only delivered to listeners when the events are committed. The payload
+            /// contains the identifier and new [version](cqrs::Version) of the saved events.
+            /// Notifications can be received using [listen](crate::postgres::EventStore::listen).
+            pub fn notify(mut self, channel: &'static str) -> Self {
+                self.notify = Some(channel);
+                self
+            }
+
+            /// Configures the partitioning of the events table.
+            ///
+            /// # Arguments
+            ///
+            /// * `value` - the [partitioning](Partitioning) of the events table
+            ///
+            /// # Remarks
+            ///
+            /// The events table is not partitioned by default. The partitioning is used by the
+            /// store migrations to create the table and cannot be changed once the table has been
+            /// created. An existing table is not converted. Tables partitioned by
+            /// [range](Partitioning::Range) require future partitions to be created periodically
+            /// using [create_partitions](crate::postgres::EventStore::create_partitions).
+            pub fn partition_by(mut self, value: Partitioning) -> Self {
+                self.partitioning = value;
+                self
             }
         }
     }
@@ -1,6 +1,12 @@
 pub(crate) mod command;
+mod lock;
+mod notify;
+mod page;
 mod store;
 
+pub use lock::Lock;
+pub use notify::Notify;
+pub(crate) use page::Pages;
 pub use store::SqlStore;
 
 use crate::SqlVersion;
@@ -69,6 +75,19 @@ pub(crate) fn select_version<T: Debug + Send>(
     }
 }
 
+pub(crate) fn select_after<T: Debug + Send>(
+    predicate: &Predicate<'_, T>,
+    mask: Option<&(dyn Mask + 'static)>,
+) -> Option<(i32, i16)> {
+    let mut version = predicate.after?.version();
+
+    if let Some(mask) = mask {
+        version = version.unmask(mask);
+    }
+
+    Some((version.number(), version.sequence()))
+}
+
 pub(crate) async fn get_snapshot<'a, ID: Debug + Send>(
     snapshots: Option<&'a dyn Store<ID>>,
     predicate: Option<&Predicate<'a, ID>>,
@@ -76,6 +95,7 @@ pub(crate) async fn get_snapshot<'a, ID: Debug + Send>(
     if let Some(snapshots) = snapshots
         && let Some(predicate) = predicate
         && predicate.load.snapshots
+        && !predicate.paged()
         && let Some(id) = predicate.id
     {
         let predicate = Some(predicate.into());
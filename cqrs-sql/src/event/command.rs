@@ -1,4 +1,6 @@
-use crate::sql::{self, greater_than, less_than};
+use crate::sql::{
+    self, BindLimit, Columns, Condition, ContentFormat, ContentType, greater_than, less_than,
+};
 use cqrs::{
     Range,
     event::{Predicate, StoreError},
@@ -7,13 +9,17 @@ use sqlx::{Database, Encode, Executor, FromRow, IntoArguments, QueryBuilder, Tra
 use std::{error::Error, fmt::Debug, ops::Bound, time::SystemTime};
 
 #[inline]
-fn and_stored_on<'a, D>(builder: &mut QueryBuilder<'a, D>, (time, op): (SystemTime, &str))
-where
+fn and_stored_on<'a, D>(
+    builder: &mut QueryBuilder<'a, D>,
+    column: &str,
+    (time, op): (SystemTime, &str),
+) where
     D: Database,
     i64: Encode<'a, D> + Type<D>,
 {
     builder
-        .push("stored_on ")
+        .push(column)
+        .push(' ')
         .push(op)
         .push(" ")
         .push_bind(crate::to_secs(time));
@@ -21,43 +27,156 @@ where
 
 pub fn select_id<'a, DB>(
     table: sql::Ident<'a>,
+    columns: &Columns,
+    stored_on: Range<SystemTime>,
+) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    i64: Encode<'a, DB> + Type<DB>,
+{
+    let mut select = select_id_from(table, columns, stored_on);
+
+    select.push(';');
+    select
+}
+
+pub fn select_id_page<'a, ID, DB>(
+    table: sql::Ident<'a>,
+    columns: &Columns,
+    stored_on: Range<SystemTime>,
+    limit: usize,
+    after: Option<&'a ID>,
+) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    ID: Encode<'a, DB> + Send + Type<DB> + 'a,
+    i64: Encode<'a, DB> + Type<DB>,
+{
+    let mut select = select_id_from(table, columns, stored_on);
+
+    if let Some(id) = after {
+        select
+            .push(" AND ")
+            .push(&columns.id)
+            .push(" > ")
+            .push_bind(id);
+    }
+
+    select
+        .push(" ORDER BY ")
+        .push(&columns.id)
+        .push(" LIMIT ")
+        .push_bind(limit as i64)
+        .push(';');
+
+    select
+}
+
+fn select_id_from<'a, DB>(
+    table: sql::Ident<'a>,
+    columns: &Columns,
     stored_on: Range<SystemTime>,
 ) -> QueryBuilder<'a, DB>
 where
     DB: Database,
     i64: Encode<'a, DB> + Type<DB>,
 {
-    let mut select = QueryBuilder::new("SELECT id FROM ");
+    let mut select = QueryBuilder::new(format!("SELECT {} FROM ", columns.id));
 
     select
         .push(table.quote())
-        .push(" WHERE version = 1 AND sequence = 0");
+        .push(" WHERE ")
+        .push(&columns.version)
+        .push(" = 1 AND ")
+        .push(&columns.sequence)
+        .push(" = 0");
 
     if let Some(lower) = greater_than(&stored_on.from) {
         select.push(" AND ");
 
         if let Some(upper) = less_than(&stored_on.to) {
             select.push('(');
-            and_stored_on(&mut select, lower);
+            and_stored_on(&mut select, &columns.stored_on, lower);
             select.push(" AND ");
-            and_stored_on(&mut select, upper);
+            and_stored_on(&mut select, &columns.stored_on, upper);
             select.push(')');
         } else {
-            and_stored_on(&mut select, lower);
+            and_stored_on(&mut select, &columns.stored_on, lower);
         }
     } else if let Some(upper) = less_than(&stored_on.to) {
         select.push(" AND ");
-        and_stored_on(&mut select, upper);
+        and_stored_on(&mut select, &columns.stored_on, upper);
     }
 
-    select.push(';');
     select
 }
 
 pub fn select<'a, ID, DB>(
     table: sql::Ident<'a>,
+    columns: &Columns,
+    content: ContentType,
+    predicate: Option<&'a Predicate<'a, ID>>,
+    version: Bound<i32>,
+    after: Option<(i32, i16)>,
+    condition: Option<Condition>,
+) -> QueryBuilder<'a, DB>
+where
+    ID: Debug + Encode<'a, DB> + Send + Type<DB> + 'a,
+    DB: Database + ContentFormat,
+    i16: Encode<'a, DB> + Type<DB>,
+    i32: Encode<'a, DB> + Type<DB>,
+    i64: Encode<'a, DB> + Type<DB>,
+    String: Encode<'a, DB> + Type<DB>,
+{
+    let init = format!(
+        "SELECT {}, {}, {}, {}, {} FROM ",
+        columns.kind,
+        columns.revision,
+        columns.version,
+        columns.sequence,
+        DB::read(&columns.content, content)
+    );
+    select_from(init, table, columns, predicate, version, after, condition)
+}
+
+pub fn select_raw<'a, ID, DB>(
+    table: sql::Ident<'a>,
+    columns: &Columns,
+    content: ContentType,
     predicate: Option<&'a Predicate<'a, ID>>,
     version: Bound<i32>,
+    after: Option<(i32, i16)>,
+    condition: Option<Condition>,
+) -> QueryBuilder<'a, DB>
+where
+    ID: Debug + Encode<'a, DB> + Send + Type<DB> + 'a,
+    DB: Database + ContentFormat,
+    i16: Encode<'a, DB> + Type<DB>,
+    i32: Encode<'a, DB> + Type<DB>,
+    i64: Encode<'a, DB> + Type<DB>,
+    String: Encode<'a, DB> + Type<DB>,
+{
+    let init = format!(
+        "SELECT {}, {}, {}, {}, {}, {}, {} FROM ",
+        columns.kind,
+        columns.revision,
+        columns.version,
+        columns.sequence,
+        DB::read(&columns.content, content),
+        columns.id,
+        columns.stored_on
+    );
+    select_from(init, table, columns, predicate, version, after, condition)
+}
+
+fn select_from<'a, ID, DB>(
+    init: String,
+    table: sql::Ident<'a>,
+    columns: &Columns,
+    predicate: Option<&'a Predicate<'a, ID>>,
+    version: Bound<i32>,
+    after: Option<(i32, i16)>,
+    condition: Option<Condition>,
 ) -> QueryBuilder<'a, DB>
 where
     ID: Debug + Encode<'a, DB> + Send + Type<DB> + 'a,
@@ -76,23 +195,43 @@ where
         }
     }
 
-    const INIT: &str = "SELECT type, revision, version, sequence, content FROM ";
-    let mut select = QueryBuilder::new(INIT);
+    fn add_condition<'a, D>(
+        builder: &mut QueryBuilder<'a, D>,
+        added: &mut bool,
+        condition: Option<Condition>,
+    ) where
+        D: Database,
+        String: Encode<'a, D> + Type<D>,
+    {
+        if let Some(condition) = condition {
+            add_where(builder, added);
+            builder
+                .push(condition.prefix)
+                .push_bind(condition.value)
+                .push(condition.suffix);
+        }
+    }
+
+    let mut select = QueryBuilder::new(init);
+    let mut added = false;
 
     select.push(table.quote());
 
     if let Some(predicate) = predicate {
-        let mut added = false;
-
         if let Some(id) = predicate.id {
-            select.push(" WHERE id = ").push_bind(id);
+            select
+                .push(" WHERE ")
+                .push(&columns.id)
+                .push(" = ")
+                .push_bind(id);
             added = true;
         }
 
         if let Some((version, op)) = greater_than(&version) {
             add_where(&mut select, &mut added);
             select
-                .push("version ")
+                .push(&columns.version)
+                .push(' ')
                 .push(op)
                 .push(" ")
                 .push_bind(version);
@@ -103,16 +242,24 @@ where
 
             if let Some(upper) = less_than(&predicate.stored_on.to) {
                 select.push('(');
-                and_stored_on(&mut select, lower);
+                and_stored_on(&mut select, &columns.stored_on, lower);
                 select.push(" AND ");
-                and_stored_on(&mut select, upper);
+                and_stored_on(&mut select, &columns.stored_on, upper);
                 select.push(')');
             } else {
-                and_stored_on(&mut select, lower);
+                and_stored_on(&mut select, &columns.stored_on, lower);
             }
         } else if let Some(upper) = less_than(&predicate.stored_on.to) {
             add_where(&mut select, &mut added);
-            and_stored_on(&mut select, upper);
+            and_stored_on(&mut select, &columns.stored_on, upper);
+        }
+
+        if let Some(cid) = predicate.correlation_id {
+            add_where(&mut select, &mut added);
+            select
+                .push(&columns.correlation_id)
+                .push(" = ")
+                .push_bind(cid.to_owned());
         }
 
         let mut schemas = predicate.types.iter();
@@ -126,11 +273,17 @@ where
                 select.push('(');
             }
 
-            select.push("(type = ").push_bind(schema.kind().to_string());
+            select
+                .push('(')
+                .push(&columns.kind)
+                .push(" = ")
+                .push_bind(schema.kind().to_string());
 
             if schema.version() > 0 {
                 select
-                    .push(" AND revision = ")
+                    .push(" AND ")
+                    .push(&columns.revision)
+                    .push(" = ")
                     .push_bind(schema.version() as i16);
             }
 
@@ -138,12 +291,16 @@ where
 
             for schema in schemas {
                 select
-                    .push(" OR (type = ")
+                    .push(" OR (")
+                    .push(&columns.kind)
+                    .push(" = ")
                     .push_bind(schema.kind().to_string());
 
                 if schema.version() > 0 {
                     select
-                        .push(" AND revision = ")
+                        .push(" AND ")
+                        .push(&columns.revision)
+                        .push(" = ")
                         .push_bind(schema.version() as i16);
                 }
 
@@ -154,6 +311,80 @@ where
                 select.push(')');
             }
         }
+
+        add_condition(&mut select, &mut added, condition);
+
+        let op = if predicate.direction.backward() {
+            " < "
+        } else {
+            " > "
+        };
+
+        if let Some((version, sequence)) = after {
+            add_where(&mut select, &mut added);
+
+            // the token's identifier only applies when reading across identifiers
+            let token = predicate.after.filter(|_| predicate.id.is_none());
+
+            if let Some(token) = token {
+                select
+                    .push('(')
+                    .push(&columns.id)
+                    .push(op)
+                    .push_bind(token.id())
+                    .push(" OR (")
+                    .push(&columns.id)
+                    .push(" = ")
+                    .push_bind(token.id())
+                    .push(" AND ");
+            }
+
+            select
+                .push('(')
+                .push(&columns.version)
+                .push(op)
+                .push_bind(version)
+                .push(" OR (")
+                .push(&columns.version)
+                .push(" = ")
+                .push_bind(version)
+                .push(" AND ")
+                .push(&columns.sequence)
+                .push(op)
+                .push_bind(sequence)
+                .push("))");
+
+            if token.is_some() {
+                select.push("))");
+            }
+        }
+
+        if predicate.paged() {
+            let order = if predicate.direction.backward() {
+                " DESC"
+            } else {
+                ""
+            };
+
+            select.push(" ORDER BY ");
+
+            if predicate.id.is_none() {
+                select.push(&columns.id).push(order).push(", ");
+            }
+
+            select
+                .push(&columns.version)
+                .push(order)
+                .push(", ")
+                .push(&columns.sequence)
+                .push(order);
+        }
+
+        if let Some(limit) = predicate.limit {
+            select.push(" LIMIT ").push_bind(limit as i64);
+        }
+    } else {
+        add_condition(&mut select, &mut added, condition);
     }
 
     select.push(';');
@@ -162,6 +393,7 @@ where
 
 pub fn exists<'a, ID, DB>(
     table: &'a sql::Ident<'a>,
+    columns: &Columns,
     previous: &'a sql::Row<ID>,
 ) -> QueryBuilder<'a, DB>
 where
@@ -174,21 +406,51 @@ where
     select
         .push(table.quote())
         .push(' ')
-        .push(" WHERE id = ")
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
         .push_bind(&previous.id)
-        .push(" AND version = ")
+        .push(" AND ")
+        .push(&columns.version)
+        .push(" = ")
         .push_bind(previous.version)
-        .push(" AND sequence = 0")
-        .push(");");
+        .push(" AND ")
+        .push(&columns.sequence)
+        .push(" = 0);");
 
     select
 }
 
-pub fn insert<'a, ID, DB>(table: &'a sql::Ident<'a>, row: &'a sql::Row<ID>) -> QueryBuilder<'a, DB>
+// id, version, sequence, revision, stored_on, type, content, correlation_id and message_id
+const BINDS_PER_ROW: usize = 9;
+
+#[cfg(test)]
+thread_local! {
+    // a batch never has enough events to exceed the bind limit of a database so tests lower the
+    // rows per insert to save a batch in several chunks
+    pub(crate) static ROWS_PER_INSERT: std::cell::Cell<Option<usize>> =
+        const { std::cell::Cell::new(None) };
+}
+
+/// Gets the maximum number of rows that can be inserted by a single statement.
+#[inline]
+pub fn rows_per_insert<DB: BindLimit>() -> usize {
+    #[cfg(test)]
+    if let Some(rows) = ROWS_PER_INSERT.get() {
+        return rows;
+    }
+
+    (DB::max_binds() / BINDS_PER_ROW).max(1)
+}
+
+pub fn insert<'a, ID, DB>(
+    table: &'a sql::Ident<'a>,
+    columns: &Columns,
+    content: ContentType,
+    rows: &'a [sql::Row<ID>],
+) -> QueryBuilder<'a, DB>
 where
-    DB: Database,
-    // for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
-    // for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
+    DB: Database + ContentFormat,
     ID: Encode<'a, DB> + Send + Type<DB> + 'a,
     i16: for<'db> Encode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + Type<DB>,
@@ -196,44 +458,202 @@ where
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db [u8]: Encode<'db, DB> + Type<DB>,
 {
+    // only name the column when it is used so that tables without it continue to work
+    let message_id = rows.iter().any(|row| row.message_id.is_some());
+    let (prefix, suffix) = DB::write(content);
     let mut insert = QueryBuilder::new("INSERT INTO ");
 
-    insert
-        .push(table.quote())
-        .push(' ')
-        .push(" VALUES (")
-        .push_bind(&row.id)
-        .push(", ")
-        .push_bind(row.version)
-        .push(", ")
-        .push_bind(row.sequence)
-        .push(", ")
-        .push_bind(row.revision)
-        .push(", ")
-        .push_bind(row.stored_on)
-        .push(", ")
-        .push_bind(&row.kind)
-        .push(", ")
-        .push_bind(row.content.as_slice())
-        .push(", ");
-
-    if let Some(cid) = &row.correlation_id {
-        insert.push_bind(cid);
-    } else {
-        insert.push("NULL");
+    insert.push(table.quote()).push(format!(
+        " ({}, {}, {}, {}, {}, {}, {}, {}",
+        columns.id,
+        columns.version,
+        columns.sequence,
+        columns.revision,
+        columns.stored_on,
+        columns.kind,
+        columns.content,
+        columns.correlation_id
+    ));
+
+    if message_id {
+        insert.push(", ").push(&columns.message_id);
     }
 
-    insert.push(");");
+    insert.push(") ").push_values(rows, |mut values, row| {
+        values
+            .push_bind(&row.id)
+            .push_bind(row.version)
+            .push_bind(row.sequence)
+            .push_bind(row.revision)
+            .push_bind(row.stored_on)
+            .push_bind(&row.kind)
+            .push(prefix)
+            .push_bind_unseparated(row.content.as_slice())
+            .push_unseparated(suffix);
+
+        if let Some(cid) = &row.correlation_id {
+            values.push_bind(cid);
+        } else {
+            values.push("NULL");
+        }
+
+        if message_id {
+            if let Some(mid) = &row.message_id {
+                values.push_bind(mid);
+            } else {
+                values.push("NULL");
+            }
+        }
+    });
+
+    insert.push(';');
     insert
 }
 
+pub fn select_max_version<'a, ID, DB>(
+    table: &'a sql::Ident<'a>,
+    columns: &Columns,
+    id: &'a ID,
+) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    ID: Encode<'a, DB> + Send + Type<DB> + 'a,
+{
+    let mut select = QueryBuilder::new(format!("SELECT MAX({}) FROM ", columns.version));
+
+    select
+        .push(table.quote())
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
+        .push_bind(id)
+        .push(';');
+
+    select
+}
+
+pub fn select_last_version<'a, ID, DB>(
+    table: &'a sql::Ident<'a>,
+    columns: &Columns,
+    id: &'a ID,
+) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    ID: Encode<'a, DB> + Send + Type<DB> + 'a,
+{
+    let mut select = QueryBuilder::new(format!(
+        "SELECT {}, {} FROM ",
+        columns.version, columns.sequence
+    ));
+
+    select
+        .push(table.quote())
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
+        .push_bind(id)
+        .push(format!(
+            " ORDER BY {} DESC, {} DESC LIMIT 1;",
+            columns.version, columns.sequence
+        ));
+
+    select
+}
+
+pub fn select_head<'a, ID, DB>(
+    table: &'a sql::Ident<'a>,
+    columns: &Columns,
+    id: &'a ID,
+) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    ID: Encode<'a, DB> + Send + Type<DB> + 'a,
+{
+    let mut select = QueryBuilder::new(format!(
+        "SELECT {}, {}, {}, (SELECT COUNT(*) FROM ",
+        columns.version, columns.sequence, columns.stored_on
+    ));
+
+    select
+        .push(table.quote())
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
+        .push_bind(id)
+        .push("), (SELECT MIN(")
+        .push(&columns.stored_on)
+        .push(") FROM ")
+        .push(table.quote())
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
+        .push_bind(id)
+        .push(") FROM ")
+        .push(table.quote())
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
+        .push_bind(id)
+        .push(format!(
+            " ORDER BY {} DESC, {} DESC LIMIT 1;",
+            columns.version, columns.sequence
+        ));
+
+    select
+}
+
+pub fn select_message<'a, ID, DB>(
+    table: &'a sql::Ident<'a>,
+    columns: &Columns,
+    id: &'a ID,
+    message_id: &str,
+) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    ID: Encode<'a, DB> + Send + Type<DB> + 'a,
+    String: Encode<'a, DB> + Type<DB>,
+{
+    let mut select = QueryBuilder::new(format!(
+        "SELECT {}, MAX({}) FROM ",
+        columns.version, columns.sequence
+    ));
+
+    select
+        .push(table.quote())
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
+        .push_bind(id)
+        .push(" AND ")
+        .push(&columns.version)
+        .push(" = (SELECT ")
+        .push(&columns.version)
+        .push(" FROM ")
+        .push(table.quote())
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
+        .push_bind(id)
+        .push(" AND ")
+        .push(&columns.message_id)
+        .push(" = ")
+        .push_bind(message_id.to_owned())
+        .push(") GROUP BY ")
+        .push(&columns.version)
+        .push(';');
+
+    select
+}
+
 pub async fn insert_transacted<'a, ID, DB>(
     table: &'a sql::Ident<'a>,
-    row: &'a sql::Row<ID>,
+    columns: &Columns,
+    content: ContentType,
+    rows: &'a [sql::Row<ID>],
     tx: &'a mut Transaction<'_, DB>,
 ) -> Result<(), StoreError<ID>>
 where
-    DB: Database,
+    DB: Database + ContentFormat,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     ID: Clone + Debug + for<'db> Encode<'db, DB> + Send + Type<DB>,
@@ -243,13 +663,15 @@ where
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db [u8]: Encode<'db, DB> + Type<DB>,
 {
-    let mut insert = insert::<ID, DB>(table, row);
+    let mut insert = insert::<ID, DB>(table, columns, content, rows);
 
     if let Err(error) = insert.build().execute(&mut **tx).await {
         if let sqlx::Error::Database(error) = &error
             && error.is_unique_violation()
         {
-            Err(StoreError::Conflict(row.id.clone(), row.version as u32))
+            // the rows are the chunk that failed, whose first row has the conflicting version
+            let first = &rows[0];
+            Err(StoreError::Conflict(first.id.clone(), first.version as u32))
         } else {
             Err(StoreError::Unknown(Box::new(error) as Box<dyn Error + Send>))
         }
@@ -260,6 +682,7 @@ where
 
 pub async fn ensure_not_deleted<'a, ID, DB>(
     table: &'a sql::Ident<'a>,
+    columns: &Columns,
     previous: &'a sql::Row<ID>,
     tx: &'a mut Transaction<'_, DB>,
 ) -> Result<(), StoreError<ID>>
@@ -271,7 +694,7 @@ where
     i32: for<'db> Encode<'db, DB> + Type<DB>,
     (bool,): for<'db> FromRow<'db, DB::Row>,
 {
-    let exists: bool = exists(table, previous)
+    let exists: bool = exists(table, columns, previous)
         .build_query_scalar()
         .fetch_one(&mut **tx)
         .await
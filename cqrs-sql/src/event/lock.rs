@@ -0,0 +1,24 @@
+use sqlx::{Database, Encode, QueryBuilder, Type};
+
+/// Defines the behavior of a SQL lock that serializes saves to the same events.
+pub trait Lock: Database {
+    /// Gets the appropriate SQL statement that locks the events for an identifier until the
+    /// current transaction ends, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the events to lock
+    ///
+    /// # Remarks
+    ///
+    /// A lock is only acquired when the events table cannot enforce unique versions by itself,
+    /// such as when it is [partitioned](crate::sql::Partitioning::Range) by range. The default
+    /// implementation does not support locks and returns `None`.
+    fn lock<'a, ID>(id: &'a ID) -> Option<QueryBuilder<'a, Self>>
+    where
+        ID: Encode<'a, Self> + Type<Self> + 'a,
+    {
+        let _ = id;
+        None
+    }
+}
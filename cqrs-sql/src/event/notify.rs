@@ -0,0 +1,28 @@
+use cqrs::Version;
+use sqlx::{Database, Encode, QueryBuilder, Type};
+
+/// Defines the behavior of a SQL notification for saved events.
+pub trait Notify: Database {
+    /// Gets the appropriate SQL statement that notifies listeners that events were saved, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - the name of the notification channel
+    /// * `id` - the identifier of the saved events
+    /// * `version` - the new [version](Version) of the saved events
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation does not support notifications and returns `None`.
+    fn notify<'a, ID>(
+        channel: &'a str,
+        id: &'a ID,
+        version: Version,
+    ) -> Option<QueryBuilder<'a, Self>>
+    where
+        ID: Encode<'a, Self> + Type<Self> + 'a,
+    {
+        let _ = (channel, id, version);
+        None
+    }
+}
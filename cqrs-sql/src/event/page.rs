@@ -0,0 +1,117 @@
+use cqrs::{
+    event::{ContinuationToken, Predicate},
+    Version,
+};
+use sqlx::{ColumnIndex, Decode, Row, Type};
+use std::fmt::Debug;
+
+// the column positions of a row selected by command::select_raw
+const VERSION: usize = 2;
+const SEQUENCE: usize = 3;
+const IDENTIFIER: usize = 5;
+
+/// Represents the position of a read that is performed in pages.
+///
+/// # Remarks
+///
+/// Each page continues after the identifier, version and sequence of the last row read, which
+/// allows a connection to be released between pages without holding a cursor open. The limit of
+/// the original predicate, if any, is applied across all of the pages.
+pub(crate) struct Pages<ID> {
+    size: usize,
+    remaining: Option<usize>,
+    after: Option<(i32, i16)>,
+    token: Option<ContinuationToken<ID>>,
+    done: bool,
+}
+
+impl<ID: Clone + Debug + Send> Pages<ID> {
+    /// Initializes a new [Pages].
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the maximum number of rows in a page
+    /// * `predicate` - the optional [predicate](Predicate) used to filter rows
+    /// * `after` - the version and sequence to read after, if any
+    pub fn new(
+        size: usize,
+        predicate: Option<&Predicate<'_, ID>>,
+        after: Option<(i32, i16)>,
+    ) -> Self {
+        let remaining = predicate.and_then(|filter| filter.limit);
+
+        Self {
+            size: size.max(1),
+            remaining,
+            after,
+            token: None,
+            done: remaining == Some(0),
+        }
+    }
+
+    /// Gets the version and sequence that the next page is read after, if any.
+    pub fn after(&self) -> Option<(i32, i16)> {
+        self.after
+    }
+
+    /// Gets the predicate used to read the next page, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - the optional [predicate](Predicate) used to filter rows
+    pub fn next<'a>(
+        &'a self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+    ) -> Option<Predicate<'a, ID>> {
+        if self.done {
+            return None;
+        }
+
+        let mut page = predicate.cloned().unwrap_or_default();
+
+        page.limit = Some(self.limit());
+
+        if let Some(token) = &self.token {
+            page.after = Some(token);
+        }
+
+        Some(page)
+    }
+
+    /// Advances the position past a page of rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - the rows read in the current page
+    pub fn advance<R>(&mut self, rows: &[R])
+    where
+        R: Row,
+        usize: ColumnIndex<R>,
+        ID: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+        i16: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+        i32: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+    {
+        let limit = self.limit();
+
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= rows.len();
+        }
+
+        self.done = rows.len() < limit || self.remaining == Some(0);
+
+        if let Some(row) = rows.last() {
+            self.after = Some((row.get(VERSION), row.get(SEQUENCE)));
+
+            // only the identifier of the token is used; the version is read from after
+            self.token = Some(ContinuationToken::new(
+                row.get(IDENTIFIER),
+                Version::default(),
+            ));
+        }
+    }
+
+    fn limit(&self) -> usize {
+        self.remaining
+            .map_or(self.size, |remaining| remaining.min(self.size))
+    }
+}
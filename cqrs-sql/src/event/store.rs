@@ -1,25 +1,42 @@
-use super::{command, get_snapshot, select_version};
+use super::{Lock, Notify, Pages, command, get_snapshot, select_after, select_version};
 use crate::{
-    BoxErr, SqlStoreBuilder, SqlVersion, SqlVersionPart, new_version,
-    sql::{self, Context, Ident, IntoRows},
+    BoxErr, SqlStoreBuilder, SqlVersion, SqlVersionPart, from_secs, new_version,
+    sql::{
+        self, BindLimit, Columns, Condition, ContentFormat, ContentType, Context, Ident, IntoRows,
+        Partitioning,
+    },
 };
 use async_stream::try_stream;
 use async_trait::async_trait;
 use cqrs::{
-    Clock, Range, Version,
-    event::{Event, EventStream, IdStream, Predicate, Store, StoreError, StoreOptions},
-    message::{Saved, Schema},
+    Clock, Concurrency, Range, Version,
+    event::{
+        Append, Event, EventStream, ExpectedVersion, Head, IdPage, IdStream, Predicate, RawEvent,
+        RawEventStream, Store, StoreError, StoreOptions,
+    },
+    message::{Descriptor, Saved, Schema},
 };
 use futures::stream;
 use sqlx::{
     ColumnIndex, Connection, Database, Decode, Encode, Executor, FromRow, IntoArguments, Pool, Row,
-    Type,
+    Transaction, Type,
+};
+use std::{
+    error::Error,
+    fmt::Debug,
+    ops::{Bound, DerefMut},
+    sync::Arc,
+    time::SystemTime,
 };
-use std::{error::Error, fmt::Debug, ops::Bound, sync::Arc, time::SystemTime};
 
 /// Represents a SQL [event store](Store).
 pub struct SqlStore<ID, DB: Database> {
     pub(crate) table: Ident<'static>,
+    pub(crate) columns: Columns,
+    pub(crate) content: ContentType,
+    pub(crate) notify: Option<&'static str>,
+    pub(crate) partitioning: Partitioning,
+    pub(crate) page_size: Option<usize>,
     pub(crate) pool: Pool<DB>,
     options: StoreOptions<ID>,
 }
@@ -35,6 +52,11 @@ impl<ID, DB: Database> SqlStore<ID, DB> {
     pub fn new(table: Ident<'static>, pool: Pool<DB>, options: StoreOptions<ID>) -> Self {
         Self {
             table,
+            columns: Columns::default(),
+            content: ContentType::default(),
+            notify: None,
+            partitioning: Partitioning::default(),
+            page_size: None,
             pool,
             options,
         }
@@ -46,8 +68,7 @@ impl<ID, DB: Database> SqlStore<ID, DB> {
     }
 }
 
-#[async_trait]
-impl<ID, DB> Store<ID> for SqlStore<ID, DB>
+impl<ID, DB> SqlStore<ID, DB>
 where
     ID: Clone
         + Debug
@@ -57,49 +78,298 @@ where
         + Sync
         + Type<DB>
         + 'static,
-    DB: Database,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
     for<'db> &'db [u8]: Encode<'db, DB> + Decode<'db, DB> + Type<DB>,
     (bool,): for<'db> FromRow<'db, DB::Row>,
 {
-    fn clock(&self) -> Arc<dyn Clock> {
-        (&self.options).into()
+    /// Loads a sequence of [events](Event) using an existing transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - the [transaction](Transaction) to load the events in
+    /// * `predicate` - the optional [predicate](Predicate) used to filter events
+    ///
+    /// # Remarks
+    ///
+    /// The transaction is owned by the caller, which allows events to be read in the same unit of
+    /// work as other application data. [Snapshots](cqrs::snapshot::Snapshot), if any, are loaded
+    /// from the configured [snapshot store](cqrs::snapshot::Store) outside of the transaction.
+    pub async fn load_in<'a>(
+        &self,
+        tx: &'a mut Transaction<'_, DB>,
+        predicate: Option<&'a Predicate<'a, ID>>,
+    ) -> EventStream<'a, ID> {
+        self.stream(&mut **tx, predicate, None).await
     }
 
-    async fn ids(&self, stored_on: Range<SystemTime>) -> IdStream<ID> {
-        let mut db = match self.pool.acquire().await.box_err() {
-            Ok(db) => db,
-            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+    /// Saves a collection of events using an existing transaction and returns the new
+    /// [version](Version), if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - the [transaction](Transaction) to save the events in
+    /// * `id` - the identifier of the events to save
+    /// * `expected_version` - the current, expected [version](Version)
+    /// * `events` - the list of [events](Event) to save
+    ///
+    /// # Remarks
+    ///
+    /// The transaction is owned by the caller, which allows events to be saved in the same unit of
+    /// work as other application data. The events are not visible to others until the caller
+    /// commits the transaction. Since the transaction cannot be retried by the store, a concurrency
+    /// [conflict](StoreError::Conflict) is always returned to the caller, who is expected to roll
+    /// back the transaction.
+    pub async fn save_in(
+        &self,
+        tx: &mut Transaction<'_, DB>,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<ID>> {
+        let append = Append::new(id.clone(), expected_version, events);
+        self.write(&append, self.options.concurrency(), tx).await
+    }
+
+    async fn append(
+        &self,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: Option<&str>,
+        concurrency: Concurrency,
+    ) -> Result<Version, StoreError<ID>> {
+        if events.is_empty() {
+            return Ok(expected_version);
+        }
+
+        if message_id.is_some() {
+            let mut db = self.pool.acquire().await.box_err()?;
+
+            if let Some(version) = self.find(id, message_id, &mut db).await? {
+                return Ok(version);
+            }
+        }
+
+        let mut version = if expected_version != Version::default()
+            && let Some(mask) = self.options.mask()
+        {
+            expected_version.unmask(mask)
+        } else {
+            expected_version
         };
-        let table = self.table.clone();
 
-        Box::pin(try_stream! {
-            let mut query = command::select_id(table, stored_on);
-            let rows = query.build().fetch(&mut *db);
+        if version.invalid() {
+            return Err(StoreError::InvalidVersion);
+        }
 
-            for await row in rows {
-                yield row.box_err()?.get::<ID, _>(0);
+        loop {
+            version = version.increment(SqlVersionPart::Version);
+
+            let context = Context {
+                id: id.clone(),
+                version,
+                clock: self.options.clock(),
+                transcoder: self.options.transcoder(),
+                message_id,
+            };
+            let mut rows = events.into_rows(context);
+            let batch = rows.by_ref().collect::<Result<Vec<_>, _>>()?;
+            let Some(first) = batch.first() else {
+                return Ok(expected_version);
+            };
+            let previous = if self.options.delete().supported() {
+                first.previous()
+            } else {
+                None
+            };
+            let chunks = batch.chunks(command::rows_per_insert::<DB>());
+            let mut db = self.pool.acquire().await.box_err()?;
+
+            let locked = self.locks();
+
+            // a notification is only delivered if the transaction that saves the events commits
+            if previous.is_none() && chunks.len() == 1 && self.notify.is_none() && !locked {
+                let mut insert = command::insert(&self.table, &self.columns, self.content, &batch);
+
+                if let Err(error) = insert.build().execute(&mut *db).await {
+                    if let sqlx::Error::Database(error) = &error
+                        && error.is_unique_violation()
+                    {
+                        if let Some(version) = self.find(id, message_id, &mut db).await? {
+                            return Ok(version);
+                        } else if concurrency.enforced() {
+                            return Err(StoreError::Conflict(id.clone(), first.version as u32));
+                        } else {
+                            continue;
+                        }
+                    }
+                    return Err(StoreError::Unknown(Box::new(error) as Box<dyn Error + Send>));
+                }
+            } else {
+                let mut tx = db.begin().await.box_err()?;
+
+                if let Some(previous) = previous {
+                    command::ensure_not_deleted(&self.table, &self.columns, &previous, &mut tx)
+                        .await?;
+                }
+
+                let mut result = if locked {
+                    self.lock(id, &mut tx).await?;
+                    self.ensure_unique(id, first.version, message_id, &mut tx)
+                        .await
+                } else {
+                    Ok(())
+                };
+
+                for chunk in chunks {
+                    if result.is_err() {
+                        break;
+                    }
+
+                    result = command::insert_transacted(
+                        &self.table,
+                        &self.columns,
+                        self.content,
+                        chunk,
+                        &mut tx,
+                    )
+                    .await;
+                }
+
+                // a conflict can occur in any chunk, such as when a concurrent save has a
+                // larger batch, and the transaction is retried the same way regardless
+                if let Err(error @ StoreError::Conflict(_, _)) = result {
+                    tx.rollback().await.box_err()?;
+
+                    // the message may have been saved concurrently
+                    if let Some(version) = self.find(id, message_id, &mut db).await? {
+                        return Ok(version);
+                    } else if concurrency.enforced() {
+                        return Err(error);
+                    } else {
+                        continue;
+                    }
+                }
+
+                result?;
+
+                self.notify(id, rows.version(), &mut tx).await?;
+                tx.commit().await.box_err()?;
             }
-        })
+
+            version = rows.version();
+            break;
+        }
+
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
+        }
+
+        Ok(version)
     }
 
-    async fn load<'a>(&self, predicate: Option<&'a Predicate<'a, ID>>) -> EventStream<'a, ID> {
-        let mut db = match self.pool.acquire().await.box_err() {
-            Ok(db) => db,
-            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+    async fn write(
+        &self,
+        append: &Append<'_, ID>,
+        concurrency: Concurrency,
+        tx: &mut Transaction<'_, DB>,
+    ) -> Result<Version, StoreError<ID>> {
+        let id = append.id();
+        let events = append.events();
+
+        if events.is_empty() {
+            return Ok(append.expected_version());
+        }
+
+        let mut version = if append.expected_version() != Version::default()
+            && let Some(mask) = self.options.mask()
+        {
+            append.expected_version().unmask(mask)
+        } else {
+            append.expected_version()
+        };
+
+        if version.invalid() {
+            return Err(StoreError::InvalidVersion);
+        }
+
+        let locked = self.locks();
+
+        if locked {
+            self.lock(id, tx).await?;
+        }
+
+        if !concurrency.enforced() {
+            let mut select = command::select_max_version(&self.table, &self.columns, id);
+            let row = select.build().fetch_one(&mut **tx).await.box_err()?;
+
+            if let Some(current) = row.get::<Option<i32>, _>(0)
+                && current > version.number()
+            {
+                version = new_version(current, 0);
+            }
+        }
+
+        let context = Context {
+            id: id.clone(),
+            version: version.increment(SqlVersionPart::Version),
+            clock: self.options.clock(),
+            transcoder: self.options.transcoder(),
+            message_id: None,
         };
-        let snapshot = match get_snapshot(self.options.snapshots(), predicate).await {
+        let mut rows = events.into_rows(context);
+        let batch = rows.by_ref().collect::<Result<Vec<_>, _>>()?;
+
+        if self.options.delete().supported()
+            && let Some(previous) = batch.first().and_then(sql::Row::previous)
+        {
+            command::ensure_not_deleted(&self.table, &self.columns, &previous, tx).await?;
+        }
+
+        if locked && let Some(first) = batch.first() {
+            self.ensure_unique(id, first.version, None, tx).await?;
+        }
+
+        for chunk in batch.chunks(command::rows_per_insert::<DB>()) {
+            command::insert_transacted(&self.table, &self.columns, self.content, chunk, tx).await?;
+        }
+
+        version = rows.version();
+        self.notify(id, version, tx).await?;
+
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
+        }
+
+        Ok(version)
+    }
+
+    pub(crate) async fn stream<'a, C>(
+        &self,
+        mut db: C,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        condition: Option<Condition>,
+    ) -> EventStream<'a, ID>
+    where
+        C: DerefMut<Target = DB::Connection> + Send + 'a,
+    {
+        // a snapshot cannot be filtered by an additional condition
+        let snapshots = self.options.snapshots().filter(|_| condition.is_none());
+        let snapshot = match get_snapshot(snapshots, predicate).await {
             Ok(snapshot) => snapshot,
             Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::from(error))])),
         };
         let table = self.table.clone();
+        let columns = self.columns.clone();
+        let content = self.content;
         let options = self.options.clone();
 
         Box::pin(try_stream! {
@@ -115,12 +385,17 @@ where
                 version = select_version(snapshot.as_ref(), filter, options.mask());
 
                 if let Some(snapshot) = snapshot {
-                    let event = options.transcoder().decode(&snapshot.schema, &snapshot.content)?;
-                    yield Saved::new(event, snapshot.version);
+                    let (schema, version) = (&snapshot.schema, snapshot.version);
+
+                    if let Some(event) = options.decode(schema, version, &snapshot.content)? {
+                        yield Saved::new(event, version);
+                    }
                 }
             }
 
-            let mut query = command::select(table, predicate, version);
+            let after = predicate.and_then(|filter| select_after(filter, options.mask()));
+            let mut query =
+                command::select(table, &columns, content, predicate, version, after, condition);
             let rows = query.build().fetch(&mut *db);
 
             for await result in rows {
@@ -130,7 +405,6 @@ where
                     row.get::<i16, _>(REVISION) as u8,
                 );
                 let content = row.get::<&[u8], _>(CONTENT);
-                let event = options.transcoder().decode(&schema, content)?;
                 let mut version = new_version(
                     row.get::<i32, _>(VERSION),
                     row.get::<i16, _>(SEQUENCE),
@@ -140,131 +414,587 @@ where
                     version = version.mask(mask);
                 }
 
-                yield Saved::new(event, version);
+                if let Some(event) = options.decode(&schema, version, content)? {
+                    yield Saved::new(event, version);
+                }
             }
         })
     }
 
-    async fn save(
+    pub(crate) async fn stream_raw<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        condition: Option<Condition>,
+    ) -> RawEventStream<'a, ID> {
+        if let Some(size) = self.page_size {
+            return self.stream_raw_pages(predicate, condition, size);
+        }
+
+        let mut db = match self.pool.acquire().await.box_err() {
+            Ok(db) => db,
+            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+        };
+        let table = self.table.clone();
+        let columns = self.columns.clone();
+        let content = self.content;
+        let options = self.options.clone();
+
+        Box::pin(try_stream! {
+            const TYPE: usize = 0;
+            const REVISION: usize = 1;
+            const VERSION: usize = 2;
+            const SEQUENCE: usize = 3;
+            const CONTENT: usize = 4;
+            const IDENTIFIER: usize = 5;
+            const STORED_ON: usize = 6;
+
+            let version = if let Some(filter) = predicate {
+                select_version(None, filter, options.mask())
+            } else {
+                Bound::Unbounded
+            };
+            let after = predicate.and_then(|filter| select_after(filter, options.mask()));
+            let mut query =
+                command::select_raw(table, &columns, content, predicate, version, after, condition);
+            let rows = query.build().fetch(&mut *db);
+
+            for await result in rows {
+                let row = result.box_err()?;
+                let schema = Schema::new(
+                    row.get::<&str, _>(TYPE),
+                    row.get::<i16, _>(REVISION) as u8,
+                );
+                let content = row.get::<&[u8], _>(CONTENT).to_vec();
+                let mut version = new_version(
+                    row.get::<i32, _>(VERSION),
+                    row.get::<i16, _>(SEQUENCE),
+                );
+
+                if let Some(mask) = options.mask() {
+                    version = version.mask(mask);
+                }
+
+                yield RawEvent::new(
+                    row.get::<ID, _>(IDENTIFIER),
+                    from_secs(row.get::<i64, _>(STORED_ON)),
+                    Descriptor::new(schema, version, content),
+                );
+            }
+        })
+    }
+
+    fn locks(&self) -> bool {
+        // uniqueness constraints of a table partitioned by range include the partition key
+        matches!(self.partitioning, Partitioning::Range(_))
+    }
+
+    async fn lock(&self, id: &ID, tx: &mut Transaction<'_, DB>) -> Result<(), StoreError<ID>> {
+        if let Some(mut lock) = DB::lock(id) {
+            let _ = lock.build().execute(&mut **tx).await.box_err()?;
+        }
+
+        Ok(())
+    }
+
+    async fn ensure_unique(
         &self,
         id: &ID,
-        expected_version: Version,
-        events: &[Box<dyn Event>],
-    ) -> Result<Version, StoreError<ID>> {
-        if events.is_empty() {
-            return Ok(expected_version);
+        version: i32,
+        message_id: Option<&str>,
+        tx: &mut Transaction<'_, DB>,
+    ) -> Result<(), StoreError<ID>> {
+        let conflict = || StoreError::Conflict(id.clone(), version as u32);
+
+        if self.find(id, message_id, &mut **tx).await?.is_some() {
+            return Err(conflict());
         }
 
-        let mut version = if expected_version != Version::default()
-            && let Some(mask) = self.options.mask()
-        {
-            expected_version.unmask(mask)
+        let mut select = command::select_max_version(&self.table, &self.columns, id);
+        let row = select.build().fetch_one(&mut **tx).await.box_err()?;
+
+        match row.get::<Option<i32>, _>(0) {
+            Some(current) if current >= version => Err(conflict()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn notify(
+        &self,
+        id: &ID,
+        version: Version,
+        tx: &mut Transaction<'_, DB>,
+    ) -> Result<(), StoreError<ID>> {
+        let Some(channel) = self.notify else {
+            return Ok(());
+        };
+        let version = if let Some(mask) = self.options.mask() {
+            version.mask(mask)
         } else {
-            expected_version
+            version
         };
 
-        if version.invalid() {
-            return Err(StoreError::InvalidVersion);
+        if let Some(mut notify) = DB::notify(channel, id, version) {
+            let _ = notify.build().execute(&mut **tx).await.box_err()?;
         }
 
-        loop {
-            version = version.increment(SqlVersionPart::Version);
+        Ok(())
+    }
 
-            let context = Context {
-                id: id.clone(),
-                version,
-                clock: self.options.clock(),
-                transcoder: self.options.transcoder(),
-            };
-            let mut rows = events.into_rows(context);
-            let first = if let Some(row) = rows.next() {
-                row?
-            } else {
-                return Ok(expected_version);
-            };
+    pub(crate) async fn stream_from_pool<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        condition: Option<Condition>,
+    ) -> EventStream<'a, ID> {
+        if let Some(size) = self.page_size {
+            return self.stream_pages(predicate, condition, size).await;
+        }
 
-            let mut db = self.pool.acquire().await.box_err()?;
+        let db = match self.pool.acquire().await.box_err() {
+            Ok(db) => db,
+            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+        };
 
-            if let Some(second) = rows.next() {
-                let mut tx = db.begin().await.box_err()?;
-                let second = second?;
+        self.stream(db, predicate, condition).await
+    }
 
-                if self.options.delete().supported()
-                    && let Some(previous) = first.previous()
-                {
-                    command::ensure_not_deleted(&self.table, &previous, &mut tx).await?;
-                }
+    async fn stream_pages<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        condition: Option<Condition>,
+        size: usize,
+    ) -> EventStream<'a, ID> {
+        // a snapshot cannot be filtered by an additional condition
+        let snapshots = self.options.snapshots().filter(|_| condition.is_none());
+        let snapshot = match get_snapshot(snapshots, predicate).await {
+            Ok(snapshot) => snapshot,
+            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::from(error))])),
+        };
+        let pool = self.pool.clone();
+        let table = self.table.clone();
+        let columns = self.columns.clone();
+        let content = self.content;
+        let options = self.options.clone();
 
-                let result = command::insert_transacted(&self.table, &first, &mut tx).await;
+        Box::pin(try_stream! {
+            const TYPE: usize = 0;
+            const REVISION: usize = 1;
+            const VERSION: usize = 2;
+            const SEQUENCE: usize = 3;
+            const CONTENT: usize = 4;
 
-                // if the first row succeeds or fails, the rest will fail or succeed
-                if matches!(result, Err(StoreError::Conflict(_, _))) {
-                    if self.options.concurrency().enforced() {
-                        result?;
-                    } else {
-                        continue;
+            let mut version = Bound::Unbounded;
+
+            if let Some(filter) = predicate {
+                version = select_version(snapshot.as_ref(), filter, options.mask());
+
+                if let Some(snapshot) = snapshot {
+                    let (schema, version) = (&snapshot.schema, snapshot.version);
+
+                    if let Some(event) = options.decode(schema, version, &snapshot.content)? {
+                        yield Saved::new(event, version);
                     }
                 }
+            }
 
-                command::insert_transacted(&self.table, &second, &mut tx).await?;
+            let after = predicate.and_then(|filter| select_after(filter, options.mask()));
+            let mut pages = Pages::new(size, predicate, after);
+
+            loop {
+                let rows = {
+                    let Some(page) = pages.next(predicate) else {
+                        break;
+                    };
+                    let mut db = pool.acquire().await.box_err()?;
+                    let mut query = command::select_raw(
+                        table.clone(),
+                        &columns,
+                        content,
+                        Some(&page),
+                        version,
+                        pages.after(),
+                        condition.clone(),
+                    );
+
+                    // the connection is released before the rows are yielded
+                    query.build().fetch_all(&mut *db).await.box_err()?
+                };
+
+                pages.advance(&rows);
+
+                for row in rows {
+                    let schema = Schema::new(
+                        row.get::<&str, _>(TYPE),
+                        row.get::<i16, _>(REVISION) as u8,
+                    );
+                    let content = row.get::<&[u8], _>(CONTENT);
+                    let mut version = new_version(
+                        row.get::<i32, _>(VERSION),
+                        row.get::<i16, _>(SEQUENCE),
+                    );
+
+                    if let Some(mask) = options.mask() {
+                        version = version.mask(mask);
+                    }
 
-                for row in rows.by_ref() {
-                    let row = row?;
-                    command::insert_transacted(&self.table, &row, &mut tx).await?;
+                    if let Some(event) = options.decode(&schema, version, content)? {
+                        yield Saved::new(event, version);
+                    }
                 }
+            }
+        })
+    }
 
-                tx.commit().await.box_err()?;
-            } else {
-                let mut execute = true;
+    fn stream_raw_pages<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        condition: Option<Condition>,
+        size: usize,
+    ) -> RawEventStream<'a, ID> {
+        let pool = self.pool.clone();
+        let table = self.table.clone();
+        let columns = self.columns.clone();
+        let content = self.content;
+        let options = self.options.clone();
 
-                if self.options.delete().supported()
-                    && let Some(previous) = first.previous()
-                {
-                    let mut tx = db.begin().await.box_err()?;
+        Box::pin(try_stream! {
+            const TYPE: usize = 0;
+            const REVISION: usize = 1;
+            const VERSION: usize = 2;
+            const SEQUENCE: usize = 3;
+            const CONTENT: usize = 4;
+            const IDENTIFIER: usize = 5;
+            const STORED_ON: usize = 6;
 
-                    command::ensure_not_deleted(&self.table, &previous, &mut tx).await?;
+            let version = if let Some(filter) = predicate {
+                select_version(None, filter, options.mask())
+            } else {
+                Bound::Unbounded
+            };
+            let after = predicate.and_then(|filter| select_after(filter, options.mask()));
+            let mut pages = Pages::new(size, predicate, after);
+
+            loop {
+                let rows = {
+                    let Some(page) = pages.next(predicate) else {
+                        break;
+                    };
+                    let mut db = pool.acquire().await.box_err()?;
+                    let mut query = command::select_raw(
+                        table.clone(),
+                        &columns,
+                        content,
+                        Some(&page),
+                        version,
+                        pages.after(),
+                        condition.clone(),
+                    );
+
+                    // the connection is released before the rows are yielded
+                    query.build().fetch_all(&mut *db).await.box_err()?
+                };
+
+                pages.advance(&rows);
+
+                for row in rows {
+                    let schema = Schema::new(
+                        row.get::<&str, _>(TYPE),
+                        row.get::<i16, _>(REVISION) as u8,
+                    );
+                    let content = row.get::<&[u8], _>(CONTENT).to_vec();
+                    let mut version = new_version(
+                        row.get::<i32, _>(VERSION),
+                        row.get::<i16, _>(SEQUENCE),
+                    );
+
+                    if let Some(mask) = options.mask() {
+                        version = version.mask(mask);
+                    }
 
-                    let result = command::insert_transacted(&self.table, &first, &mut tx).await;
+                    yield RawEvent::new(
+                        row.get::<ID, _>(IDENTIFIER),
+                        from_secs(row.get::<i64, _>(STORED_ON)),
+                        Descriptor::new(schema, version, content),
+                    );
+                }
+            }
+        })
+    }
 
-                    if matches!(result, Err(StoreError::Conflict(_, _))) {
-                        if self.options.concurrency().enforced() {
-                            result?;
-                        } else {
-                            continue;
-                        }
-                    }
+    fn ids_in_pages(&self, stored_on: Range<SystemTime>, size: usize) -> IdStream<ID> {
+        let pool = self.pool.clone();
+        let table = self.table.clone();
+        let columns = self.columns.clone();
+        let size = size.max(1);
 
-                    tx.commit().await.box_err()?;
-                    execute = false;
+        Box::pin(try_stream! {
+            let mut after = None;
+
+            loop {
+                let ids = {
+                    let mut db = pool.acquire().await.box_err()?;
+                    let mut query = command::select_id_page(
+                        table.clone(),
+                        &columns,
+                        stored_on.clone(),
+                        size,
+                        after.as_ref(),
+                    );
+                    let rows = query.build().fetch_all(&mut *db).await.box_err()?;
+
+                    rows.iter().map(|row| row.get::<ID, _>(0)).collect::<Vec<_>>()
+                };
+                let last = ids.len() < size;
+
+                after = ids.last().cloned();
+
+                for id in ids {
+                    yield id;
                 }
 
-                if execute {
-                    let mut insert = command::insert(&self.table, &first);
-
-                    if let Err(error) = insert.build().execute(&mut *db).await {
-                        if let sqlx::Error::Database(error) = &error
-                            && error.is_unique_violation()
-                        {
-                            if self.options.concurrency().enforced() {
-                                return Err(StoreError::Conflict(id.clone(), first.version as u32));
-                            } else {
-                                continue;
-                            }
-                        }
-                        return Err(StoreError::Unknown(Box::new(error) as Box<dyn Error + Send>));
-                    }
+                if last {
+                    break;
                 }
             }
+        })
+    }
 
-            version = rows.version();
-            break;
+    async fn find(
+        &self,
+        id: &ID,
+        message_id: Option<&str>,
+        db: &mut DB::Connection,
+    ) -> Result<Option<Version>, StoreError<ID>> {
+        if let Some(message_id) = message_id {
+            let mut select = command::select_message(&self.table, &self.columns, id, message_id);
+
+            if let Some(row) = select.build().fetch_optional(&mut *db).await.box_err()? {
+                let mut version = new_version(row.get::<i32, _>(0), row.get::<i16, _>(1));
+
+                if let Some(mask) = self.options.mask() {
+                    version = version.mask(mask);
+                }
+
+                return Ok(Some(version));
+            }
         }
 
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl<ID, DB> Store<ID> for SqlStore<ID, DB>
+where
+    ID: Clone
+        + Debug
+        + for<'db> Encode<'db, DB>
+        + for<'db> Decode<'db, DB>
+        + Send
+        + Sync
+        + Type<DB>
+        + 'static,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify,
+    for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
+    for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
+    i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
+    i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
+    usize: ColumnIndex<<DB as Database>::Row>,
+    String: for<'db> Encode<'db, DB> + Type<DB>,
+    for<'db> &'db str: Decode<'db, DB> + Type<DB>,
+    for<'db> &'db [u8]: Encode<'db, DB> + Decode<'db, DB> + Type<DB>,
+    (bool,): for<'db> FromRow<'db, DB::Row>,
+{
+    fn clock(&self) -> Arc<dyn Clock> {
+        (&self.options).into()
+    }
+
+    async fn ids(&self, stored_on: Range<SystemTime>) -> IdStream<ID> {
+        if let Some(size) = self.page_size {
+            return self.ids_in_pages(stored_on, size);
+        }
+
+        let mut db = match self.pool.acquire().await.box_err() {
+            Ok(db) => db,
+            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+        };
+        let table = self.table.clone();
+        let columns = self.columns.clone();
+
+        Box::pin(try_stream! {
+            let mut query = command::select_id(table, &columns, stored_on);
+            let rows = query.build().fetch(&mut *db);
+
+            for await row in rows {
+                yield row.box_err()?.get::<ID, _>(0);
+            }
+        })
+    }
+
+    async fn ids_page(
+        &self,
+        stored_on: Range<SystemTime>,
+        limit: usize,
+        after: Option<&ID>,
+    ) -> Result<IdPage<ID>, StoreError<ID>> {
+        let mut db = self.pool.acquire().await.box_err()?;
+        let mut query =
+            command::select_id_page(self.table.clone(), &self.columns, stored_on, limit, after);
+        let rows = query.build().fetch_all(&mut *db).await.box_err()?;
+        let ids = rows.iter().map(|row| row.get::<ID, _>(0)).collect();
+
+        Ok(IdPage::of(ids, limit))
+    }
+
+    async fn load<'a>(&self, predicate: Option<&'a Predicate<'a, ID>>) -> EventStream<'a, ID> {
+        self.stream_from_pool(predicate, None).await
+    }
+
+    async fn load_raw<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+    ) -> RawEventStream<'a, ID> {
+        self.stream_raw(predicate, None).await
+    }
+
+    async fn head(&self, id: &ID) -> Result<Head<ID>, StoreError<ID>> {
+        const VERSION: usize = 0;
+        const SEQUENCE: usize = 1;
+        const STORED_ON: usize = 2;
+        const COUNT: usize = 3;
+        const FIRST_STORED_ON: usize = 4;
+
+        let mut db = self.pool.acquire().await.box_err()?;
+        let mut select = command::select_head(&self.table, &self.columns, id);
+        let Some(row) = select.build().fetch_optional(&mut *db).await.box_err()? else {
+            return Ok(Head::empty(id.clone()));
+        };
+        let mut version = new_version(row.get::<i32, _>(VERSION), row.get::<i16, _>(SEQUENCE));
+
         if let Some(mask) = self.options.mask() {
             version = version.mask(mask);
         }
 
-        Ok(version)
+        Ok(Head::new(
+            id.clone(),
+            version,
+            row.get::<i64, _>(COUNT) as u64,
+            from_secs(row.get::<i64, _>(FIRST_STORED_ON)),
+            from_secs(row.get::<i64, _>(STORED_ON)),
+        ))
+    }
+
+    async fn save(
+        &self,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<ID>> {
+        let concurrency = self.options.concurrency();
+        self.append(id, expected_version, events, None, concurrency)
+            .await
+    }
+
+    async fn save_expecting(
+        &self,
+        id: &ID,
+        expected: ExpectedVersion,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<ID>> {
+        let expected_version = match expected {
+            ExpectedVersion::Exact(version) => {
+                let mut db = self.pool.acquire().await.box_err()?;
+                let mut tx = db.begin().await.box_err()?;
+                let mut select = command::select_last_version(&self.table, &self.columns, id);
+
+                // the current version is read in the same transaction that appends the events so
+                // that an expectation ahead of the stream cannot leave a gap in its versions
+                let Some(row) = select.build().fetch_optional(&mut *tx).await.box_err()? else {
+                    return Err(StoreError::NotFound(id.clone()));
+                };
+                let current = new_version(row.get::<i32, _>(0), row.get::<i16, _>(1));
+                let expected_version = if let Some(mask) = self.options.mask() {
+                    version.unmask(mask)
+                } else {
+                    version
+                };
+
+                if current != expected_version {
+                    let version = expected_version.number() as u32;
+                    return Err(StoreError::Conflict(id.clone(), version));
+                }
+
+                let version = self
+                    .write(
+                        &Append::new(id.clone(), version, events),
+                        expected.concurrency(),
+                        &mut tx,
+                    )
+                    .await?;
+
+                tx.commit().await.box_err()?;
+                return Ok(version);
+            }
+            ExpectedVersion::NoStream => Version::default(),
+            ExpectedVersion::Any | ExpectedVersion::StreamExists => {
+                let mut db = self.pool.acquire().await.box_err()?;
+                let mut select = command::select_max_version(&self.table, &self.columns, id);
+                let row = select.build().fetch_one(&mut *db).await.box_err()?;
+
+                if let Some(current) = row.get::<Option<i32>, _>(0) {
+                    let mut version = new_version(current, 0);
+
+                    if let Some(mask) = self.options.mask() {
+                        version = version.mask(mask);
+                    }
+
+                    version
+                } else if expected == ExpectedVersion::StreamExists {
+                    return Err(StoreError::NotFound(id.clone()));
+                } else {
+                    Version::default()
+                }
+            }
+        };
+
+        self.append(id, expected_version, events, None, expected.concurrency())
+            .await
+    }
+
+    async fn save_once(
+        &self,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: &str,
+    ) -> Result<Version, StoreError<ID>> {
+        let concurrency = self.options.concurrency();
+        self.append(id, expected_version, events, Some(message_id), concurrency)
+            .await
+    }
+
+    async fn save_all(&self, appends: &[Append<'_, ID>]) -> Result<Vec<Version>, StoreError<ID>> {
+        let concurrency = self.options.concurrency();
+        let mut db = self.pool.acquire().await.box_err()?;
+
+        'retry: loop {
+            let mut tx = db.begin().await.box_err()?;
+            let mut versions = Vec::with_capacity(appends.len());
+
+            for append in appends {
+                match self.write(append, concurrency, &mut tx).await {
+                    Ok(version) => versions.push(version),
+                    Err(StoreError::Conflict(_, _)) if !concurrency.enforced() => {
+                        tx.rollback().await.box_err()?;
+                        continue 'retry;
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+
+            tx.commit().await.box_err()?;
+            return Ok(versions);
+        }
     }
 
     async fn delete(&self, id: &ID) -> Result<(), StoreError<ID>> {
@@ -274,7 +1004,7 @@ where
 
         let mut db = self.pool.acquire().await.box_err()?;
         let mut tx = db.begin().await.box_err()?;
-        let mut delete = sql::command::delete(&self.table, id);
+        let mut delete = sql::command::delete(&self.table, &self.columns, id);
         let _ = delete.build().execute(&mut *tx).await.box_err()?;
 
         if let Some(snapshots) = self.options.snapshots() {
@@ -3,6 +3,9 @@ mod builder;
 /// Provides event storage using a SQL database.
 pub mod event;
 
+/// Provides projection storage using a SQL database.
+pub mod projection;
+
 /// Provides snapshot storage using a SQL database.
 pub mod snapshot;
 
@@ -36,7 +39,7 @@ pub use providers::sqlite;
 use cfg_if::cfg_if;
 use std::{
     error::Error,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 cfg_if! {
@@ -59,3 +62,7 @@ impl<T, E: Error + Send + 'static> BoxErr<T> for Result<T, E> {
 pub(crate) fn to_secs(timestamp: SystemTime) -> i64 {
     timestamp.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
 }
+
+pub(crate) fn from_secs(secs: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs as u64)
+}
@@ -1,5 +1,7 @@
 mod migration;
 mod migrator;
+mod script;
 
 pub use migration::SqlStoreMigration;
 pub use migrator::SqlStoreMigrator;
+pub(crate) use script::{index_name, migrations, Script};
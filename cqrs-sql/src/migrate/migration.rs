@@ -5,7 +5,6 @@ use sqlx::{
     pool::PoolOptions,
     Database, Pool,
 };
-use std::borrow::Cow;
 
 enum Either<DB: Database> {
     Pool(Pool<DB>),
@@ -13,17 +12,22 @@ enum Either<DB: Database> {
 }
 
 #[derive(Debug)]
-struct Source(Migration);
+struct Source(Vec<Migration>);
 
 impl<'s> MigrationSource<'s> for Source {
     fn resolve(self) -> BoxFuture<'s, Result<Vec<Migration>, BoxDynError>> {
-        Box::pin(future::ready(Ok(vec![self.0])))
+        Box::pin(future::ready(Ok(self.0)))
     }
 }
 
 /// Represents the migration for a SQL-based storage.
+///
+/// # Remarks
+///
+/// A migration is comprised of an ordered list of versioned migrations. Only the versions that
+/// have not yet been applied to the target database are run.
 pub struct SqlStoreMigration<DB: Database> {
-    migration: Migration,
+    migrations: Vec<Migration>,
     url: String,
     either: Either<DB>,
 }
@@ -37,16 +41,16 @@ where
     ///
     ///  # Argument
     ///
-    /// * `migration` - the migration to execute
+    /// * `migrations` - the ordered list of migrations to execute
     /// * `url` - the URL representing the database connection string
     /// * `options` - the [connection pool options](PoolOptions) used during the migration
-    pub fn new<M, S>(migration: M, url: S, options: PoolOptions<DB>) -> Self
+    pub fn new<M, S>(migrations: M, url: S, options: PoolOptions<DB>) -> Self
     where
-        M: Into<Migration>,
+        M: Into<Vec<Migration>>,
         S: AsRef<str>,
     {
         Self {
-            migration: migration.into(),
+            migrations: sorted(migrations.into()),
             url: url.as_ref().into(),
             either: Either::Options(options),
         }
@@ -56,11 +60,11 @@ where
     ///
     ///  # Argument
     ///
-    /// * `migration` - the migration to execute
+    /// * `migrations` - the ordered list of migrations to execute
     /// * `pool` - the [connection pool](Pool) used during the migration
-    pub fn with_pool(migration: impl Into<Migration>, pool: Pool<DB>) -> Self {
+    pub fn with_pool(migrations: impl Into<Vec<Migration>>, pool: Pool<DB>) -> Self {
         Self {
-            migration: migration.into(),
+            migrations: sorted(migrations.into()),
             url: Default::default(),
             either: Either::Pool(pool),
         }
@@ -71,9 +75,12 @@ where
         &self.url
     }
 
-    /// Gets the migration version.
+    /// Gets the latest migration version.
     pub fn version(&self) -> i64 {
-        self.migration.version
+        self.migrations
+            .last()
+            .map(|m| m.version)
+            .unwrap_or_default()
     }
 
     /// Merges one migration into another.
@@ -81,22 +88,31 @@ where
     /// # Arguments
     ///
     /// * `other` - the other [migration](SqlStoreMigration) to merge
+    ///
+    /// # Remarks
+    ///
+    /// Each table has its own version namespace so the migrations of different tables never share
+    /// a version. A migration with a version that already exists, such as the same table
+    /// configured twice, is only run once.
     pub fn merge(&mut self, other: Self) {
-        let m1 = &self.migration;
-        let m2 = other.migration;
+        for m2 in other.migrations {
+            if !self.migrations.iter().any(|m1| m1.version == m2.version) {
+                self.migrations.push(m2);
+            }
+        }
 
-        self.migration = Migration::new(
-            m1.version,
-            Cow::Owned(format!("{} {}", m1.description, m2.description)),
-            m1.migration_type,
-            Cow::Owned(format!("{}\n{}", m1.sql, m2.sql)),
-            m1.no_tx,
-        )
+        self.migrations.sort_by_key(|m| m.version);
     }
 
     /// Runs the migration.
+    ///
+    /// # Remarks
+    ///
+    /// Previously applied versions that are not part of this migration, such as those of another
+    /// table in the same database, are ignored.
     pub async fn run(self) -> Result<(), MigrateError> {
-        let migrator = Migrator::new(Source(self.migration)).await?;
+        let mut migrator = Migrator::new(Source(self.migrations)).await?;
+        migrator.set_ignore_missing(true);
         let pool = match self.either {
             Either::Pool(pool) => pool,
             Either::Options(options) => options.connect(&self.url).await?,
@@ -105,8 +121,13 @@ where
     }
 }
 
-impl<DB: Database> From<SqlStoreMigration<DB>> for Migration {
+impl<DB: Database> From<SqlStoreMigration<DB>> for Vec<Migration> {
     fn from(value: SqlStoreMigration<DB>) -> Self {
-        value.migration
+        value.migrations
     }
 }
+
+fn sorted(mut migrations: Vec<Migration>) -> Vec<Migration> {
+    migrations.sort_by_key(|m| m.version);
+    migrations
+}
@@ -43,18 +43,18 @@ where
     ///
     /// # Arguments
     ///
-    /// * `migration` - the [migration](Migration) to configure
+    /// * `migrations` - the ordered list of [migrations](Migration) to configure
     /// * `url` - the URL representing the database connection string
     /// * `options` - the [connection pool options](PoolOptions) to use during the migration
     #[inline]
-    pub fn configure<M, S>(&self, migration: M, url: S, options: PoolOptions<DB>)
+    pub fn configure<M, S>(&self, migrations: M, url: S, options: PoolOptions<DB>)
     where
-        M: Into<Migration>,
+        M: Into<Vec<Migration>>,
         S: AsRef<str>,
         DB: Database,
         DB::Connection: Migrate,
     {
-        self.add(SqlStoreMigration::new(migration, url, options));
+        self.add(SqlStoreMigration::new(migrations, url, options));
     }
 
     /// Adds a migration.
@@ -70,7 +70,7 @@ where
         let mut migrations = self.migrations.lock().unwrap();
 
         for existing in migrations.iter_mut() {
-            if existing.version() == migration.version() && existing.url() == migration.url() {
+            if existing.url() == migration.url() {
                 existing.merge(migration);
                 return;
             }
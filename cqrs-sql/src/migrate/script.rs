@@ -0,0 +1,110 @@
+use crate::sql::{Columns, ContentType, Ident, Partitioning};
+use sqlx::migrate::{Migration, MigrationType::Simple};
+use std::borrow::Cow;
+
+/// Represents a single, incremental migration script for a storage table.
+///
+/// # Remarks
+///
+/// A script is versioned by its position in an ordered list of scripts, starting at `1`, within
+/// the version namespace of its table. A script must never change once it has been released
+/// because its checksum is validated against the previously applied migrations. New columns or
+/// indexes are introduced by appending a script.
+pub(crate) struct Script {
+    /// Gets the description of the script.
+    pub description: &'static str,
+
+    /// Gets the function that generates the SQL for a table, its columns, identifier type, content
+    /// type and partitioning.
+    pub sql: fn(&Ident, &Columns, &str, ContentType, Partitioning) -> String,
+}
+
+/// Creates the versioned migrations for a table from an ordered list of scripts.
+///
+/// # Arguments
+///
+/// * `table` - the [identifier](Ident) of the table to migrate
+/// * `columns` - the table [columns](Columns)
+/// * `id_type` - the database type of the identifier column
+/// * `content_type` - the storage [type](ContentType) of the content column
+/// * `partitioning` - the [partitioning](Partitioning) of the table
+/// * `scripts` - the ordered list of [scripts](Script) to create migrations for
+pub(crate) fn migrations(
+    table: &Ident,
+    columns: &Columns,
+    id_type: &str,
+    content_type: ContentType,
+    partitioning: Partitioning,
+    scripts: &[Script],
+) -> Vec<Migration> {
+    let namespace = namespace(table);
+
+    scripts
+        .iter()
+        .zip(1..)
+        .map(|(script, version)| {
+            Migration::new(
+                namespace + version,
+                Cow::Owned(format!("'{}' {}.", table.name(), script.description)),
+                Simple,
+                Cow::Owned((script.sql)(
+                    table,
+                    columns,
+                    id_type,
+                    content_type,
+                    partitioning,
+                )),
+                false,
+            )
+        })
+        .collect()
+}
+
+/// Gets the version namespace of a table.
+///
+/// # Arguments
+///
+/// * `table` - the [identifier](Ident) of the table
+///
+/// # Remarks
+///
+/// Every table on the same database shares a single migration history. The namespace is derived
+/// from a stable FNV-1a hash of the qualified table name so that the versions of one table never
+/// collide with, or depend on, the versions of another table. Each namespace holds up to
+/// [`NAMESPACE_SIZE`] - 1 scripts.
+fn namespace(table: &Ident) -> i64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let schema = table.schema().unwrap_or_default();
+    let hash = schema
+        .bytes()
+        .chain(std::iter::once(b'.'))
+        .chain(table.name().bytes())
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(PRIME)
+        });
+
+    // keep 40 bits so the scaled namespace fits comfortably in a signed 64-bit version
+    ((hash >> 24) as i64) * NAMESPACE_SIZE
+}
+
+/// The number of versions reserved for each table namespace.
+const NAMESPACE_SIZE: i64 = 1000;
+
+/// Creates the unqualified name of an index for a table.
+///
+/// # Arguments
+///
+/// * `prefix` - the prefix of the index name
+/// * `table` - the [identifier](Ident) of the indexed table
+/// * `suffix` - the suffix of the index name, which is typically the indexed column names
+pub(crate) fn index_name(prefix: &str, table: &Ident, suffix: &str) -> String {
+    let name: String = table
+        .name()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    format!("{prefix}_{name}_{suffix}")
+}
@@ -1,7 +1,8 @@
 use super::{DynEventStore, DynSnapshotStore, SqlOptions, merge};
 use crate::{
-    event,
+    event::{self, Lock, Notify},
     snapshot::{self, Prune, Upsert},
+    sql::{BindLimit, Columns, ContentFormat, ContentType, Partitioning},
 };
 use cfg_if::cfg_if;
 use cqrs::{
@@ -23,12 +24,12 @@ pub struct SqlStoreBuilder<'a, A, DB: Database>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
@@ -45,12 +46,12 @@ impl<'a, A, DB> SqlStoreBuilder<'a, A, DB>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
@@ -91,12 +92,12 @@ impl<'a, A, DB> Drop for SqlStoreBuilder<'a, A, DB>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
@@ -145,12 +146,12 @@ pub struct SqlStoreOptionsBuilder<'a, A, DB: Database>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
@@ -160,9 +161,14 @@ where
     pub(crate) parent: SqlStoreBuilder<'a, A, DB>,
     pub(crate) url: Option<String>,
     pub(crate) options: Option<PoolOptions<DB>>,
+    pub(crate) columns: Columns,
+    pub(crate) content: ContentType,
+    pub(crate) notify: Option<&'static str>,
+    pub(crate) partitioning: Partitioning,
     pub(crate) mask: Option<Box<dyn Mask>>,
     pub(crate) enforce_concurrency: bool,
     pub(crate) allow_delete: bool,
+    pub(crate) page_size: Option<usize>,
     pub(crate) use_snapshots: bool,
 }
 
@@ -170,12 +176,12 @@ impl<'a, A, DB> SqlStoreOptionsBuilder<'a, A, DB>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
@@ -187,9 +193,14 @@ where
             parent,
             url: None,
             options: None,
+            columns: Columns::default(),
+            content: ContentType::default(),
+            notify: None,
+            partitioning: Partitioning::default(),
             mask: None,
             enforce_concurrency: false,
             allow_delete: false,
+            page_size: None,
             use_snapshots: false,
         }
     }
@@ -239,6 +250,16 @@ where
         self
     }
 
+    /// Configures the mapping of the columns in the event and snapshot tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the table [columns](Columns)
+    pub fn columns(mut self, value: Columns) -> Self {
+        self.columns = value;
+        self
+    }
+
     /// Configures the associated mask.
     ///
     /// # Arguments
@@ -260,18 +281,29 @@ where
         self.allow_delete = true;
         self
     }
+
+    /// Reads events and identifiers in pages of the specified size, which holds a pooled connection
+    /// for the lifetime of each stream by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the maximum number of rows read in each page
+    pub fn page_size(mut self, value: usize) -> Self {
+        self.page_size = Some(value);
+        self
+    }
 }
 
 impl<'a, A, DB> SqlStoreOptionsBuilder<'a, A, DB>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database + for<'db> Prune<'db, A::ID, DB> + Upsert,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify + for<'db> Prune<'db, A::ID, DB> + Upsert,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
@@ -289,6 +321,7 @@ where
         let name = self.parent.name;
         let url = self.url.clone();
         let cfg_options = self.options.clone();
+        let columns = self.columns.clone();
         let mask = self.mask.take().map(Arc::from);
 
         self.use_snapshots = true;
@@ -303,6 +336,7 @@ where
                     let mut builder = merge(
                         snapshot::SqlStore::<A::ID, DB>::builder()
                             .table(name)
+                            .columns(columns.clone())
                             .clock(sp.get_required::<dyn Clock>())
                             .transcoder(sp.get_required::<Transcoder<dyn Snapshot>>()),
                         name,
@@ -327,12 +361,12 @@ impl<'a, A, DB> Drop for SqlStoreOptionsBuilder<'a, A, DB>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
@@ -343,9 +377,14 @@ where
         let name = self.parent.name;
         let url = self.url.clone();
         let cfg_options = self.options.clone();
+        let columns = self.columns.clone();
+        let content = self.content;
+        let notify = self.notify;
+        let partitioning = self.partitioning;
         let mask = self.mask.take().map(Arc::from);
         let enforce_concurrency = self.enforce_concurrency;
         let allow_delete = self.allow_delete;
+        let page_size = self.page_size;
 
         self.parent.services.try_add(
             singleton_with_key::<A, DynEventStore<A::ID>, event::SqlStore<A::ID, DB>>()
@@ -359,6 +398,7 @@ where
                     let mut builder = merge(
                         event::SqlStore::<A::ID, DB>::builder()
                             .table(name)
+                            .columns(columns.clone())
                             .clock(sp.get_required::<dyn Clock>())
                             .transcoder(sp.get_required::<Transcoder<dyn Event>>()),
                         name,
@@ -367,6 +407,10 @@ where
                         di_options.as_ref(),
                     );
 
+                    builder.content = content;
+                    builder.notify = notify;
+                    builder.partitioning = partitioning;
+
                     if let Some(snapshots) = sp.get_by_key::<A, DynSnapshotStore<A::ID>>() {
                         builder =
                             builder.snapshots(Ref::<DynSnapshotStore<A::ID>>::from(snapshots));
@@ -384,6 +428,10 @@ where
                         builder = builder.with_deletes();
                     }
 
+                    if let Some(size) = page_size {
+                        builder = builder.page_size(size);
+                    }
+
                     Ref::new(builder.build().unwrap())
                 }),
         );
@@ -399,19 +447,19 @@ cfg_if! {
         where
             A: Aggregate + Default + Sync + 'static,
             A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-            DB: Database + Upsert,
+            DB: Database + BindLimit + ContentFormat + Lock + Notify + Upsert,
             <DB as Database>::Connection: Migrate,
             for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
             for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
             i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
             i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-            i64: for<'db> Encode<'db, DB> + Type<DB>,
+            i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
             usize: ColumnIndex<<DB as Database>::Row>,
             String: for<'db> Encode<'db, DB> + Type<DB>,
             for<'db> &'db str: Decode<'db, DB> + Type<DB>,
             for<'db> &'db [u8]: Encode<'db, DB> + Decode<'db, DB> + Type<DB>,
-            for<'c> &'c event::SqlStore<A::ID, DB>: Into<Migration>,
-            for<'c> &'c snapshot::SqlStore<A::ID, DB>: Into<Migration>,
+            for<'c> &'c event::SqlStore<A::ID, DB>: Into<Vec<Migration>>,
+            for<'c> &'c snapshot::SqlStore<A::ID, DB>: Into<Vec<Migration>>,
             (bool,): for<'db> FromRow<'db, DB::Row>,
         {
             /// Configures the database to use migrations.
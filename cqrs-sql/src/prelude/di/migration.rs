@@ -1,7 +1,9 @@
 use super::{DynSnapshotStore, SqlOptions, SqlStoreOptionsBuilder, merge};
 use crate::{
-    SqlStoreMigration, SqlStoreMigrator, event,
+    SqlStoreMigration, SqlStoreMigrator,
+    event::{self, Lock, Notify},
     snapshot::{self, Upsert},
+    sql::{BindLimit, ContentFormat},
 };
 use cqrs::{Aggregate, Clock, event::Event, message::Transcoder};
 use di::{Injectable, Ref, exactly_one, transient_as_self, zero_or_one, zero_or_one_with_key};
@@ -16,19 +18,19 @@ pub struct SqlMigrationsBuilder<'a, A, DB>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database + Upsert,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify + Upsert,
     <DB as Database>::Connection: Migrate,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
     for<'db> &'db [u8]: Encode<'db, DB> + Decode<'db, DB> + Type<DB>,
-    for<'c> &'c event::SqlStore<A::ID, DB>: Into<Migration>,
-    for<'c> &'c snapshot::SqlStore<A::ID, DB>: Into<Migration>,
+    for<'c> &'c event::SqlStore<A::ID, DB>: Into<Vec<Migration>>,
+    for<'c> &'c snapshot::SqlStore<A::ID, DB>: Into<Vec<Migration>>,
     (bool,): for<'db> FromRow<'db, DB::Row>,
 {
     parent: SqlStoreOptionsBuilder<'a, A, DB>,
@@ -38,19 +40,19 @@ impl<'a, A, DB> SqlMigrationsBuilder<'a, A, DB>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database + Upsert,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify + Upsert,
     <DB as Database>::Connection: Migrate,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
     for<'db> &'db [u8]: Encode<'db, DB> + Decode<'db, DB> + Type<DB>,
-    for<'c> &'c event::SqlStore<A::ID, DB>: Into<Migration>,
-    for<'c> &'c snapshot::SqlStore<A::ID, DB>: Into<Migration>,
+    for<'c> &'c event::SqlStore<A::ID, DB>: Into<Vec<Migration>>,
+    for<'c> &'c snapshot::SqlStore<A::ID, DB>: Into<Vec<Migration>>,
     (bool,): for<'db> FromRow<'db, DB::Row>,
 {
     pub(crate) fn new(parent: SqlStoreOptionsBuilder<'a, A, DB>) -> Self {
@@ -66,25 +68,28 @@ impl<'a, A, DB> Drop for SqlMigrationsBuilder<'a, A, DB>
 where
     A: Aggregate + Default + Sync + 'static,
     A::ID: Clone + for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Sync + Type<DB>,
-    DB: Database + Upsert,
+    DB: Database + BindLimit + ContentFormat + Lock + Notify + Upsert,
     <DB as Database>::Connection: Migrate,
     for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
     for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
     i16: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     i32: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
-    i64: for<'db> Encode<'db, DB> + Type<DB>,
+    i64: for<'db> Encode<'db, DB> + for<'db> Decode<'db, DB> + Type<DB>,
     usize: ColumnIndex<<DB as Database>::Row>,
     String: for<'db> Encode<'db, DB> + Type<DB>,
     for<'db> &'db str: Decode<'db, DB> + Type<DB>,
     for<'db> &'db [u8]: Encode<'db, DB> + Decode<'db, DB> + Type<DB>,
-    for<'c> &'c event::SqlStore<A::ID, DB>: Into<Migration>,
-    for<'c> &'c snapshot::SqlStore<A::ID, DB>: Into<Migration>,
+    for<'c> &'c event::SqlStore<A::ID, DB>: Into<Vec<Migration>>,
+    for<'c> &'c snapshot::SqlStore<A::ID, DB>: Into<Vec<Migration>>,
     (bool,): for<'db> FromRow<'db, DB::Row>,
 {
     fn drop(&mut self) {
         let name = self.parent.parent.name;
         let url = self.parent.url.clone();
         let cfg_options = self.parent.options.clone();
+        let columns = self.parent.columns.clone();
+        let content = self.parent.content;
+        let partitioning = self.parent.partitioning;
 
         self.parent.parent.services.add(
             transient_as_self::<SqlStoreMigration<DB>>()
@@ -93,9 +98,10 @@ where
                 .depends_on(zero_or_one::<dyn OptionsSnapshot<SqlOptions<DB>>>())
                 .from(move |sp| {
                     let di_options = sp.get::<dyn OptionsSnapshot<SqlOptions<DB>>>();
-                    let builder = merge(
+                    let mut builder = merge(
                         event::SqlStore::<A::ID, DB>::builder()
                             .table(name)
+                            .columns(columns.clone())
                             .clock(sp.get_required::<dyn Clock>())
                             .transcoder(sp.get_required::<Transcoder<dyn Event>>()),
                         name,
@@ -103,6 +109,9 @@ where
                         cfg_options.as_ref(),
                         di_options.as_ref(),
                     );
+                    builder.content = content;
+                    builder.partitioning = partitioning;
+
                     let url = builder.url.clone().unwrap_or_default();
                     let options = builder.options.clone().unwrap_or_default();
                     let store = builder.build().unwrap();
@@ -118,6 +127,7 @@ where
 
         let url = self.parent.url.clone();
         let cfg_options = self.parent.options.clone();
+        let columns = self.parent.columns.clone();
 
         self.parent.parent.services.add(
             transient_as_self::<SqlStoreMigration<DB>>()
@@ -130,6 +140,7 @@ where
                     let builder = merge(
                         event::SqlStore::<A::ID, DB>::builder()
                             .table(name)
+                            .columns(columns.clone())
                             .clock(sp.get_required::<dyn Clock>())
                             .transcoder(sp.get_required::<Transcoder<dyn Event>>()),
                         name,
@@ -9,7 +9,7 @@ where
     A::ID: Clone + for<'db> Encode<'db, MySql> + for<'db> Decode<'db, MySql> + Sync + Type<MySql>,
     i16: for<'db> Encode<'db, MySql> + for<'db> Decode<'db, MySql> + Type<MySql>,
     i32: for<'db> Encode<'db, MySql> + Type<MySql>,
-    i64: for<'db> Encode<'db, MySql> + Type<MySql>,
+    i64: for<'db> Encode<'db, MySql> + for<'db> Decode<'db, MySql> + Type<MySql>,
     usize: ColumnIndex<<MySql as Database>::Row>,
     String: for<'db> Encode<'db, MySql> + Type<MySql>,
     for<'db> &'db str: Decode<'db, MySql> + Type<MySql>,
@@ -25,7 +25,7 @@ where
     A::ID: Clone + for<'db> Encode<'db, MySql> + for<'db> Decode<'db, MySql> + Sync + Type<MySql>,
     i16: for<'db> Encode<'db, MySql> + for<'db> Decode<'db, MySql> + Type<MySql>,
     i32: for<'db> Encode<'db, MySql> + Type<MySql>,
-    i64: for<'db> Encode<'db, MySql> + Type<MySql>,
+    i64: for<'db> Encode<'db, MySql> + for<'db> Decode<'db, MySql> + Type<MySql>,
     usize: ColumnIndex<<MySql as Database>::Row>,
     String: for<'db> Encode<'db, MySql> + Type<MySql>,
     for<'db> &'db str: Decode<'db, MySql> + Type<MySql>,
@@ -1,4 +1,5 @@
-use super::SqlStoreBuilder;
+use super::{SqlStoreBuilder, SqlStoreOptionsBuilder};
+use crate::sql::{ContentType, Partitioning};
 use cqrs::{Aggregate, prelude::AggregateBuilder};
 use sqlx::{ColumnIndex, Database, Decode, Encode, Postgres, Type};
 
@@ -13,7 +14,7 @@ where
         + Type<Postgres>,
     i16: for<'db> Encode<'db, Postgres> + for<'db> Decode<'db, Postgres> + Type<Postgres>,
     i32: for<'db> Encode<'db, Postgres> + Type<Postgres>,
-    i64: for<'db> Encode<'db, Postgres> + Type<Postgres>,
+    i64: for<'db> Encode<'db, Postgres> + for<'db> Decode<'db, Postgres> + Type<Postgres>,
     usize: ColumnIndex<<Postgres as Database>::Row>,
     String: for<'db> Encode<'db, Postgres> + Type<Postgres>,
     for<'db> &'db str: Decode<'db, Postgres> + Type<Postgres>,
@@ -33,7 +34,7 @@ where
         + Type<Postgres>,
     i16: for<'db> Encode<'db, Postgres> + for<'db> Decode<'db, Postgres> + Type<Postgres>,
     i32: for<'db> Encode<'db, Postgres> + Type<Postgres>,
-    i64: for<'db> Encode<'db, Postgres> + Type<Postgres>,
+    i64: for<'db> Encode<'db, Postgres> + for<'db> Decode<'db, Postgres> + Type<Postgres>,
     usize: ColumnIndex<<Postgres as Database>::Row>,
     String: for<'db> Encode<'db, Postgres> + Type<Postgres>,
     for<'db> &'db str: Decode<'db, Postgres> + Type<Postgres>,
@@ -43,3 +44,58 @@ where
         SqlStoreBuilder::new(self.services)
     }
 }
+
+impl<'a, A> SqlStoreOptionsBuilder<'a, A, Postgres>
+where
+    A: Aggregate + Default + Sync + 'static,
+    A::ID: Clone
+        + for<'db> Encode<'db, Postgres>
+        + for<'db> Decode<'db, Postgres>
+        + Sync
+        + Type<Postgres>,
+{
+    /// Configures the storage type of the event content.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the storage [type](ContentType) of the event content
+    ///
+    /// # Remarks
+    ///
+    /// Events encoded as JSON can be stored as [JSON](ContentType::Json), which allows the event
+    /// content to be queried. Snapshots are always stored as [binary](ContentType::Binary).
+    pub fn content_type(mut self, value: ContentType) -> Self {
+        self.content = value;
+        self
+    }
+
+    /// Configures the channel that is notified when events are saved.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - the name of the notification channel
+    ///
+    /// # Remarks
+    ///
+    /// Listeners are notified of the identifier and new version of the saved events once they are
+    /// committed. Refer to [listen](crate::postgres::EventStore::listen) to receive notifications.
+    pub fn notify(mut self, channel: &'static str) -> Self {
+        self.notify = Some(channel);
+        self
+    }
+
+    /// Configures the partitioning of the events table.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the [partitioning](Partitioning) of the events table
+    ///
+    /// # Remarks
+    ///
+    /// The partitioning must be configured before the events table is created by the migrations.
+    /// Snapshots are never partitioned.
+    pub fn partition_by(mut self, value: Partitioning) -> Self {
+        self.partitioning = value;
+        self
+    }
+}
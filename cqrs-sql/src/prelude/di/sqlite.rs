@@ -1,5 +1,8 @@
 use super::{DynEventStore, DynSnapshotStore, SqlOptions, merge};
-use crate::sqlite::{EventStore, SnapshotStore};
+use crate::{
+    sql::Columns,
+    sqlite::{EventStore, SnapshotStore},
+};
 use cfg_if::cfg_if;
 use cqrs::{
     Aggregate, Clock, Mask, Repository, prelude::AggregateBuilder, event::Event, message::Transcoder,
@@ -130,9 +133,11 @@ where
     parent: SqliteStoreBuilder<'a, A>,
     url: Option<String>,
     options: Option<PoolOptions<Sqlite>>,
+    columns: Columns,
     mask: Option<Box<dyn Mask>>,
     enforce_concurrency: bool,
     allow_delete: bool,
+    page_size: Option<usize>,
     use_snapshots: bool,
 }
 
@@ -147,9 +152,11 @@ where
             parent,
             url: None,
             options: None,
+            columns: Columns::default(),
             mask: None,
             enforce_concurrency: false,
             allow_delete: false,
+            page_size: None,
             use_snapshots: false,
         }
     }
@@ -199,6 +206,16 @@ where
         self
     }
 
+    /// Configures the mapping of the columns in the event and snapshot tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the table [columns](Columns)
+    pub fn columns(mut self, value: Columns) -> Self {
+        self.columns = value;
+        self
+    }
+
     /// Configures the associated mask.
     ///
     /// # Arguments
@@ -221,6 +238,17 @@ where
         self
     }
 
+    /// Reads events and identifiers in pages of the specified size, which holds a pooled connection
+    /// for the lifetime of each stream by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the maximum number of rows read in each page
+    pub fn page_size(mut self, value: usize) -> Self {
+        self.page_size = Some(value);
+        self
+    }
+
     /// Configures storage with SQL-based snapshots.
     ///
     /// # Remarks
@@ -232,6 +260,7 @@ where
         let name = self.parent.name;
         let url = self.url.clone();
         let cfg_options = self.options.clone();
+        let columns = self.columns.clone();
         let mask = self.mask.take().map(Arc::from);
 
         self.use_snapshots = true;
@@ -248,6 +277,7 @@ where
                         let mut builder = merge(
                             SnapshotStore::<A::ID>::builder()
                                 .table(name)
+                                .columns(columns.clone())
                                 .clock(sp.get_required::<dyn Clock>())
                                 .transcoder(sp.get_required::<Transcoder<dyn Snapshot>>()),
                             name,
@@ -285,9 +315,11 @@ where
         let name = self.parent.name;
         let url = self.url.clone();
         let cfg_options = self.options.clone();
+        let columns = self.columns.clone();
         let mask = self.mask.take().map(Arc::from);
         let enforce_concurrency = self.enforce_concurrency;
         let allow_delete = self.allow_delete;
+        let page_size = self.page_size;
 
         self.parent
             .services
@@ -303,6 +335,7 @@ where
                         let mut builder = merge(
                             EventStore::<A::ID>::builder()
                                 .table(name)
+                                .columns(columns.clone())
                                 .clock(sp.get_required::<dyn Clock>())
                                 .transcoder(sp.get_required::<Transcoder<dyn Event>>()),
                             name,
@@ -328,6 +361,10 @@ where
                             builder = builder.with_deletes();
                         }
 
+                        if let Some(size) = page_size {
+                            builder = builder.page_size(size);
+                        }
+
                         Ref::new(EventStore::try_from(builder).unwrap())
                     }),
             )
@@ -342,14 +379,18 @@ where
 
 cfg_if! {
     if #[cfg(feature = "migrate")] {
-        use crate::{SqlStoreMigration, SqlStoreMigrator};
+        use crate::{sql::SqlIdType, SqlStoreMigration, SqlStoreMigrator};
         use di::{transient_as_self, Injectable};
 
         /// Represents the configuration for [SQLite](Sqlite) storage migration.
         pub struct SqliteMigrationsBuilder<'a, A>
         where
             A: Aggregate + Default + Sync + 'static,
-            A::ID: Clone + for<'db> Encode<'db, Sqlite> + for<'db> Decode<'db, Sqlite> + Sync + Type<Sqlite>,
+            A::ID: Clone
+                + for<'db> Encode<'db, Sqlite>
+                + for<'db> Decode<'db, Sqlite>
+                + Sync
+                + SqlIdType<Sqlite>,
         {
             parent: SqliteStoreOptionsBuilder<'a, A>,
         }
@@ -357,7 +398,11 @@ cfg_if! {
         impl<'a, A> SqliteMigrationsBuilder<'a, A>
         where
             A: Aggregate + Default + Sync + 'static,
-            A::ID: Clone + for<'db> Encode<'db, Sqlite> + for<'db> Decode<'db, Sqlite> + Sync + Type<Sqlite>,
+            A::ID: Clone
+                + for<'db> Encode<'db, Sqlite>
+                + for<'db> Decode<'db, Sqlite>
+                + Sync
+                + SqlIdType<Sqlite>,
         {
             fn new(parent: SqliteStoreOptionsBuilder<'a, A>) -> Self {
                 parent
@@ -371,7 +416,11 @@ cfg_if! {
         impl<'a, A> Drop for SqliteMigrationsBuilder<'a, A>
         where
             A: Aggregate + Default + Sync + 'static,
-            A::ID: Clone + for<'db> Encode<'db, Sqlite> + for<'db> Decode<'db, Sqlite> + Sync + Type<Sqlite>,
+            A::ID: Clone
+                + for<'db> Encode<'db, Sqlite>
+                + for<'db> Decode<'db, Sqlite>
+                + Sync
+                + SqlIdType<Sqlite>,
         {
             fn drop(&mut self) {
                 self.parent.parent.services.add(
@@ -405,8 +454,11 @@ cfg_if! {
         impl<'a, A> SqliteStoreOptionsBuilder<'a, A>
         where
             A: Aggregate + Default + Sync + 'static,
-            A::ID:
-                Clone + for<'db> Encode<'db, Sqlite> + for<'db> Decode<'db, Sqlite> + Sync + Type<Sqlite>,
+            A::ID: Clone
+                + for<'db> Encode<'db, Sqlite>
+                + for<'db> Decode<'db, Sqlite>
+                + Sync
+                + SqlIdType<Sqlite>,
         {
             /// Configures the database to use migrations.
             pub fn migrations(self) -> SqliteMigrationsBuilder<'a, A> {
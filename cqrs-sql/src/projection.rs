@@ -0,0 +1,8 @@
+pub(crate) mod command;
+mod model;
+mod sink;
+mod upsert;
+
+pub use model::ReadModel;
+pub use sink::SqlSink;
+pub use upsert::Upsert;
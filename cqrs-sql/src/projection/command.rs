@@ -0,0 +1,50 @@
+use super::{ReadModel, Upsert};
+use crate::sql::Ident;
+use sqlx::{Database, Encode, QueryBuilder, Type};
+
+pub fn upsert<'a, ID, P, DB>(table: &'a Ident<'a>, id: &'a ID, model: &'a P) -> QueryBuilder<'a, DB>
+where
+    DB: Database + Upsert,
+    ID: Encode<'a, DB> + Send + Type<DB> + 'a,
+    P: ReadModel<DB>,
+    i64: for<'db> Encode<'db, DB> + Type<DB>,
+{
+    let columns = P::columns();
+    let mut insert = QueryBuilder::new("INSERT INTO ");
+
+    insert.push(table.quote()).push(" (id, checkpoint");
+
+    for column in columns {
+        insert.push(", ").push(Ident::unqualified(*column).quote());
+    }
+
+    insert.push(") VALUES (");
+
+    let mut values = insert.separated(", ");
+
+    values.push_bind(id).push_bind(model.checkpoint());
+    model.bind(&mut values);
+
+    insert
+        .push(") ")
+        .push(DB::on_conflict(table, columns))
+        .push(';');
+
+    insert
+}
+
+pub fn select_checkpoint<'a, ID, DB>(table: &'a Ident<'a>, id: &'a ID) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    ID: Encode<'a, DB> + Send + Type<DB> + 'a,
+{
+    let mut select = QueryBuilder::new("SELECT checkpoint FROM ");
+
+    select
+        .push(table.quote())
+        .push(" WHERE id = ")
+        .push_bind(id)
+        .push(';');
+
+    select
+}
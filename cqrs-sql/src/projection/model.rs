@@ -0,0 +1,30 @@
+use sqlx::{Database, query_builder::Separated};
+
+/// Defines the behavior of a projection that is stored as a read model in a SQL table.
+///
+/// # Remarks
+///
+/// A read model table must have an `id` column, which is the primary key, and a `checkpoint`
+/// column that can hold a 64-bit integer, followed by the [columns](ReadModel::columns) of the
+/// read model.
+pub trait ReadModel<DB: Database>: Send + Sync {
+    /// Gets the names of the read model columns, excluding the `id` and `checkpoint` columns.
+    fn columns() -> &'static [&'static str];
+
+    /// Gets the checkpoint of the read model.
+    ///
+    /// # Remarks
+    ///
+    /// The checkpoint is a monotonically increasing position, such as the number of the last
+    /// [version](cqrs::Version) projected or the time the last event was stored on. An existing
+    /// read model is only replaced by one with the same or a later checkpoint.
+    fn checkpoint(&self) -> i64;
+
+    /// Binds the read model column values.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - the [separated](Separated) values to bind to in the order of the
+    ///   [columns](ReadModel::columns)
+    fn bind<'a>(&'a self, values: &mut Separated<'_, 'a, DB, &'static str>);
+}
@@ -0,0 +1,80 @@
+use super::{ReadModel, Upsert, command};
+use crate::{BoxErr, sql::Ident};
+use async_trait::async_trait;
+use cqrs::projection::Sink;
+use sqlx::{ColumnIndex, Database, Decode, Encode, Executor, IntoArguments, Pool, Type};
+use std::error::Error;
+
+/// Represents a SQL [sink](Sink) that stores projections as [read models](ReadModel).
+///
+/// # Remarks
+///
+/// Each projection is upserted by its identifier along with its checkpoint. A projection with an
+/// earlier checkpoint than the stored read model is ignored, which makes writes idempotent.
+pub struct SqlSink<DB: Database> {
+    table: Ident<'static>,
+    pool: Pool<DB>,
+}
+
+impl<DB: Database> SqlSink<DB> {
+    /// Initializes a new [SqlSink].
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - the read model table [identifier](Ident)
+    /// * `pool` - the underlying [connection pool](Pool)
+    ///
+    /// # Remarks
+    ///
+    /// The [connection pool](Pool) can be the same pool used by an event store.
+    pub fn new(table: Ident<'static>, pool: Pool<DB>) -> Self {
+        Self { table, pool }
+    }
+}
+
+impl<DB> SqlSink<DB>
+where
+    DB: Database,
+    for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
+    for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
+    i64: for<'db> Decode<'db, DB> + Type<DB>,
+    usize: ColumnIndex<<DB as Database>::Row>,
+{
+    /// Gets the checkpoint of the read model stored for the specified identifier, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the read model
+    pub async fn checkpoint<ID>(&self, id: &ID) -> Result<Option<i64>, Box<dyn Error + Send>>
+    where
+        ID: for<'db> Encode<'db, DB> + Send + Sync + Type<DB>,
+    {
+        let mut db = self.pool.acquire().await.box_err()?;
+        let mut select = command::select_checkpoint(&self.table, id);
+
+        select
+            .build_query_scalar()
+            .fetch_optional(&mut *db)
+            .await
+            .box_err()
+    }
+}
+
+#[async_trait]
+impl<ID, P, DB> Sink<ID, P> for SqlSink<DB>
+where
+    ID: for<'db> Encode<'db, DB> + Send + Sync + Type<DB>,
+    P: ReadModel<DB> + 'static,
+    DB: Database + Upsert,
+    for<'args, 'db> <DB as Database>::Arguments<'args>: IntoArguments<'db, DB>,
+    for<'db> &'db mut <DB as Database>::Connection: Executor<'db, Database = DB>,
+    i64: for<'db> Encode<'db, DB> + Type<DB>,
+{
+    async fn write(&self, id: &ID, projection: P) -> Result<(), Box<dyn Error + Send>> {
+        let mut db = self.pool.acquire().await.box_err()?;
+        let mut upsert = command::upsert(&self.table, id, &projection);
+        let _ = upsert.build().execute(&mut *db).await.box_err()?;
+
+        Ok(())
+    }
+}
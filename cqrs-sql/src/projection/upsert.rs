@@ -0,0 +1,17 @@
+use crate::sql::Ident;
+
+/// Defines the behavior of a SQL upsert for read models.
+pub trait Upsert {
+    /// Gets the appropriate SQL `ON CONFLICT` upsert clause.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - the read model table [identifier](Ident)
+    /// * `columns` - the read model columns, excluding the `id` and `checkpoint` columns
+    ///
+    /// # Remarks
+    ///
+    /// The clause must only update an existing row when its checkpoint is less than or equal to
+    /// the checkpoint being written so that replaying or reordering writes is idempotent.
+    fn on_conflict(table: &Ident, columns: &[&str]) -> String;
+}
@@ -1,3 +1,16 @@
+// implements SqlIdType for a database and a list of identifier types
+macro_rules! sql_id_type {
+    ($db:ty, $($type:ty => $sql:literal),+ $(,)?) => {
+        $(
+            impl crate::sql::SqlIdType<$db> for $type {
+                fn sql_type() -> &'static str {
+                    $sql
+                }
+            }
+        )+
+    };
+}
+
 #[cfg(feature = "mysql")]
 /// Provides SQL storage support for MySQL.
 pub mod mysql;
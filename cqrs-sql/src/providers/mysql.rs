@@ -1,25 +1,69 @@
-use crate::{event, snapshot, sql};
+use crate::{event, projection, snapshot, sql};
 use cqrs::{snapshot::Retention, Clock};
 use sqlx::{Encode, MySql, QueryBuilder, Type};
-use std::time::UNIX_EPOCH;
+use std::{fmt::Write, time::UNIX_EPOCH};
+use uuid::Uuid;
 
 impl snapshot::Upsert for MySql {
-    fn on_conflict() -> &'static str {
-        concat!(
-            "ON DUPLICATE KEY UPDATE ",
-            "taken_on = VALUES(taken_on), ",
-            "revision = VALUES(revision), ",
-            "type = VALUES(type)"
+    fn on_conflict(columns: &sql::Columns) -> String {
+        format!(
+            "ON DUPLICATE KEY UPDATE {0} = VALUES({0}), {1} = VALUES({1}), {2} = VALUES({2})",
+            columns.taken_on, columns.revision, columns.kind
         )
     }
 }
 
+impl sql::BindLimit for MySql {
+    fn max_binds() -> usize {
+        u16::MAX as usize
+    }
+}
+
+impl sql::ContentFormat for MySql {}
+
+impl event::Lock for MySql {}
+
+impl event::Notify for MySql {}
+
+sql_id_type!(
+    MySql,
+    i8 => "TINYINT",
+    u8 => "TINYINT",
+    i16 => "SMALLINT",
+    u16 => "SMALLINT",
+    i32 => "INT",
+    u32 => "INT",
+    i64 => "BIGINT",
+    u64 => "BIGINT",
+    Uuid => "BINARY(16)",
+    String => "VARCHAR(50)",
+);
+
+impl projection::Upsert for MySql {
+    fn on_conflict(_table: &sql::Ident, columns: &[&str]) -> String {
+        let mut clause = String::from("ON DUPLICATE KEY UPDATE ");
+
+        // the checkpoint must be updated last because assignments are evaluated from left to right
+        for column in columns {
+            let column = sql::Ident::unqualified(*column).quote();
+            let _ = write!(
+                clause,
+                "{column} = IF(checkpoint <= VALUES(checkpoint), VALUES({column}), {column}), "
+            );
+        }
+
+        clause.push_str("checkpoint = GREATEST(checkpoint, VALUES(checkpoint))");
+        clause
+    }
+}
+
 impl<'a, ID> snapshot::Prune<'a, ID, MySql> for MySql
 where
     ID: Encode<'a, MySql> + Type<MySql>,
 {
     fn prune(
         table: &'a sql::Ident<'a>,
+        columns: &'a sql::Columns,
         id: &'a ID,
         clock: &'a dyn Clock,
         retention: &'a Retention,
@@ -27,9 +71,11 @@ where
         let mut delete = QueryBuilder::new("WITH s2 AS (");
 
         delete
-            .push("SELECT id, version FROM ")
+            .push(format!("SELECT {}, {} FROM ", columns.id, columns.version))
             .push(table.quote())
-            .push("WHERE id = ")
+            .push(" WHERE ")
+            .push(&columns.id)
+            .push(" = ")
             .push_bind(id);
 
         // SAFETY: unwrap is allowed here as before epoch is a bug in the clock
@@ -38,24 +84,33 @@ where
             if let Some(age) = retention.age {
                 let taken_on = (clock.now() - age).duration_since(UNIX_EPOCH).unwrap();
                 delete
-                    .push(" AND taken_on >= ")
+                    .push(" AND ")
+                    .push(&columns.taken_on)
+                    .push(" >= ")
                     .push_bind(taken_on.as_secs() as i64);
             }
 
             delete
-                .push(" ORDER BY taken_on DESC LIMIT 18446744073709551615 OFFSET ")
+                .push(" ORDER BY ")
+                .push(&columns.taken_on)
+                .push(" DESC LIMIT 18446744073709551615 OFFSET ")
                 .push_bind(count as i16);
         } else if let Some(age) = retention.age {
             let taken_on = (clock.now() - age).duration_since(UNIX_EPOCH).unwrap();
             delete
-                .push(" AND taken_on <= ")
+                .push(" AND ")
+                .push(&columns.taken_on)
+                .push(" <= ")
                 .push_bind(taken_on.as_secs() as i64);
         }
 
         delete
             .push(") DELETE s1 FROM ")
             .push(table.quote())
-            .push(" s1 INNER JOIN s2 WHERE s1.id = s2.id AND s1.version = s2.version;");
+            .push(format!(
+                " s1 INNER JOIN s2 WHERE s1.{0} = s2.{0} AND s1.{1} = s2.{1};",
+                columns.id, columns.version
+            ));
 
         delete
     }
@@ -67,6 +122,9 @@ pub type EventStore<ID> = event::SqlStore<ID, MySql>;
 /// Represents a MySql [snapshot store](snapshot::SqlStore).
 pub type SnapshotStore<ID> = snapshot::SqlStore<ID, MySql>;
 
+/// Represents a MySQL [projection sink](projection::SqlSink).
+pub type ProjectionSink = projection::SqlSink<MySql>;
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "migrate")] {
         mod migration;
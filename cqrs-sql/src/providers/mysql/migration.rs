@@ -1,64 +1,73 @@
+use crate::migrate::{index_name, migrations, Script};
 use crate::SqlStoreMigrator;
 use crate::{
     mysql,
-    sql::{Ident, IdentPart::Schema},
+    sql::{
+        Columns,
+        ContentType::{self, Binary},
+        Ident,
+        IdentPart::Schema,
+        Partitioning, SqlIdType,
+    },
 };
-use sqlx::{
-    migrate::{Migration, MigrationType::Simple},
-    MySql,
-};
-use std::any::type_name;
-use std::borrow::Cow;
-use std::mem::size_of;
+use sqlx::{migrate::Migration, MySql};
 
 /// Represents a MySql [migrator](SqlStoreMigrator).
 pub type Migrator = SqlStoreMigrator<MySql>;
 
-impl<ID> From<&mysql::EventStore<ID>> for Migration {
+const EVENTS: &[Script] = &[
+    Script {
+        description: "events table",
+        sql: events_table,
+    },
+    Script {
+        description: "events message identifier",
+        sql: events_message_id,
+    },
+    Script {
+        description: "events secondary indexes",
+        sql: events_indexes,
+    },
+];
+
+const SNAPSHOTS: &[Script] = &[Script {
+    description: "snapshots table",
+    sql: snapshots_table,
+}];
+
+impl<ID: SqlIdType<MySql>> From<&mysql::EventStore<ID>> for Vec<Migration> {
     fn from(value: &mysql::EventStore<ID>) -> Self {
-        Self::new(
-            1,
-            Cow::Owned(format!("'{}' events table.", value.table.name())),
-            Simple,
-            Cow::Owned(events_table(&value.table, db_type::<ID>())),
-            false,
+        migrations(
+            &value.table,
+            &value.columns,
+            ID::sql_type(),
+            Binary,
+            Partitioning::None,
+            EVENTS,
         )
     }
 }
 
-impl<ID> From<&mysql::SnapshotStore<ID>> for Migration {
+impl<ID: SqlIdType<MySql>> From<&mysql::SnapshotStore<ID>> for Vec<Migration> {
     fn from(value: &mysql::SnapshotStore<ID>) -> Self {
-        Self::new(
-            1,
-            Cow::Owned(format!("'{}' snapshots table.", value.table.name())),
-            Simple,
-            Cow::Owned(snapshots_table(&value.table, db_type::<ID>())),
-            false,
+        migrations(
+            &value.table,
+            &value.columns,
+            ID::sql_type(),
+            Binary,
+            Partitioning::None,
+            SNAPSHOTS,
         )
     }
 }
 
-#[inline]
-fn db_type<ID>() -> &'static str {
-    let name = type_name::<ID>();
-
-    match name {
-        "i8" | "u8" => "TINYINT",
-        "i16" | "u16" => "SMALLINT",
-        "i32" | "u32" => "INT",
-        "i64" | "u64" => "BIGINT",
-        "isize" | "usize" => match size_of::<isize>() {
-            4 => "INT",
-            8 => "BIGINT",
-            _ => unreachable!(),
-        },
-        "uuid::Uuid" => "BINARY(16)",
-        "alloc::string::String" => "VARCHAR(50)",
-        _ => panic!("type '{}' is not a supported database type", name),
-    }
-}
-
-fn events_table(table: &Ident, db_type: &str) -> String {
+fn events_table(
+    table: &Ident,
+    columns: &Columns,
+    db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
     let mut sql = String::new();
 
     if let Some(schema) = table.quote_part(Schema) {
@@ -70,23 +79,82 @@ fn events_table(table: &Ident, db_type: &str) -> String {
     sql.push_str("CREATE TABLE IF NOT EXISTS ");
     sql.push_str(&table.quote());
     sql.push('(');
-    sql.push_str("id ");
+    sql.push_str(&columns.id);
+    sql.push(' ');
     sql.push_str(db_type);
     sql.push_str(" NOT NULL, ");
-    sql.push_str("version INT NOT NULL, ");
-    sql.push_str("sequence TINYINT NOT NULL, ");
-    sql.push_str("revision TINYINT NOT NULL, ");
-    sql.push_str("stored_on BIGINT NOT NULL, ");
-    sql.push_str("type VARCHAR(128) NOT NULL, ");
-    sql.push_str("content BLOB NOT NULL, ");
-    sql.push_str("correlation_id VARCHAR(50) DEFAULT NULL, ");
-    sql.push_str("PRIMARY KEY(id, version, sequence)");
+    sql.push_str(&columns.version);
+    sql.push_str(" INT NOT NULL, ");
+    sql.push_str(&columns.sequence);
+    sql.push_str(" TINYINT NOT NULL, ");
+    sql.push_str(&columns.revision);
+    sql.push_str(" TINYINT NOT NULL, ");
+    sql.push_str(&columns.stored_on);
+    sql.push_str(" BIGINT NOT NULL, ");
+    sql.push_str(&columns.kind);
+    sql.push_str(" VARCHAR(128) NOT NULL, ");
+    sql.push_str(&columns.content);
+    sql.push_str(" BLOB NOT NULL, ");
+    sql.push_str(&columns.correlation_id);
+    sql.push_str(" VARCHAR(50) DEFAULT NULL, ");
+    sql.push_str(&format!(
+        "PRIMARY KEY({}, {}, {})",
+        columns.id, columns.version, columns.sequence
+    ));
     sql.push_str(");");
 
     sql
 }
 
-fn snapshots_table(table: &Ident, db_type: &str) -> String {
+fn events_message_id(
+    table: &Ident,
+    columns: &Columns,
+    _db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
+    let mut sql = String::new();
+
+    sql.push_str("ALTER TABLE ");
+    sql.push_str(&table.quote());
+    sql.push_str(" ADD COLUMN ");
+    sql.push_str(&columns.message_id);
+    sql.push_str(" VARCHAR(50) DEFAULT NULL, ");
+    sql.push_str("ADD UNIQUE INDEX ");
+    sql.push_str(&index_name("ux", table, "message_id"));
+    sql.push_str(&format!("({}, {});", columns.id, columns.message_id));
+
+    sql
+}
+
+fn events_indexes(
+    table: &Ident,
+    columns: &Columns,
+    _db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
+    let mut sql = String::new();
+
+    sql.push_str("ALTER TABLE ");
+    sql.push_str(&table.quote());
+    sql.push_str(" ADD INDEX ");
+    sql.push_str(&index_name("ix", table, "stored_on"));
+    sql.push_str(&format!("({}), ", columns.stored_on));
+    sql.push_str("ADD INDEX ");
+    sql.push_str(&index_name("ix", table, "type_revision"));
+    sql.push_str(&format!("({}, {});", columns.kind, columns.revision));
+
+    sql
+}
+
+fn snapshots_table(
+    table: &Ident,
+    columns: &Columns,
+    db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
     let mut sql = String::new();
 
     if let Some(schema) = table.quote_part(Schema) {
@@ -98,16 +166,23 @@ fn snapshots_table(table: &Ident, db_type: &str) -> String {
     sql.push_str("CREATE TABLE IF NOT EXISTS ");
     sql.push_str(&table.quote());
     sql.push('(');
-    sql.push_str("id ");
+    sql.push_str(&columns.id);
+    sql.push(' ');
     sql.push_str(db_type);
     sql.push_str(" NOT NULL, ");
-    sql.push_str("version INT NOT NULL, ");
-    sql.push_str("revision TINYINT NOT NULL, ");
-    sql.push_str("taken_on BIGINT NOT NULL, ");
-    sql.push_str("type VARCHAR(128) NOT NULL, ");
-    sql.push_str("content BLOB NOT NULL, ");
-    sql.push_str("correlation_id VARCHAR(50) DEFAULT NULL, ");
-    sql.push_str("PRIMARY KEY(id, version)");
+    sql.push_str(&columns.version);
+    sql.push_str(" INT NOT NULL, ");
+    sql.push_str(&columns.revision);
+    sql.push_str(" TINYINT NOT NULL, ");
+    sql.push_str(&columns.taken_on);
+    sql.push_str(" BIGINT NOT NULL, ");
+    sql.push_str(&columns.kind);
+    sql.push_str(" VARCHAR(128) NOT NULL, ");
+    sql.push_str(&columns.content);
+    sql.push_str(" BLOB NOT NULL, ");
+    sql.push_str(&columns.correlation_id);
+    sql.push_str(" VARCHAR(50) DEFAULT NULL, ");
+    sql.push_str(&format!("PRIMARY KEY({}, {})", columns.id, columns.version));
     sql.push_str(");");
 
     sql
@@ -1,25 +1,122 @@
-use crate::{event, snapshot, sql};
-use cqrs::{snapshot::Retention, Clock};
+mod import;
+mod json;
+mod listen;
+mod partition;
+
+pub use import::{HistoricalEvent, ImportError};
+pub use listen::{Notification, NotificationStream};
+
+use crate::{event, projection, snapshot, sql};
+use cqrs::{snapshot::Retention, Clock, Version};
 use sqlx::{Encode, Postgres, QueryBuilder, Type};
-use std::time::UNIX_EPOCH;
+use std::{borrow::Cow, fmt::Write, time::UNIX_EPOCH};
+use uuid::Uuid;
 
 impl snapshot::Upsert for Postgres {
-    fn on_conflict() -> &'static str {
-        concat!(
-            "ON CONFLICT (id, version) DO UPDATE SET ",
-            "taken_on = EXCLUDED.taken_on, ",
-            "revision = EXCLUDED.revision, ",
-            "type = EXCLUDED.type"
+    fn on_conflict(columns: &sql::Columns) -> String {
+        format!(
+            "ON CONFLICT ({0}, {1}) DO UPDATE SET \
+             {2} = EXCLUDED.{2}, {3} = EXCLUDED.{3}, {4} = EXCLUDED.{4}",
+            columns.id, columns.version, columns.taken_on, columns.revision, columns.kind
         )
     }
 }
 
+impl sql::BindLimit for Postgres {
+    fn max_binds() -> usize {
+        u16::MAX as usize
+    }
+}
+
+impl sql::ContentFormat for Postgres {
+    fn read(column: &str, content_type: sql::ContentType) -> Cow<'_, str> {
+        match content_type {
+            sql::ContentType::Binary => Cow::Borrowed(column),
+            sql::ContentType::Json => Cow::Owned(format!("convert_to({column}::text, 'UTF8')")),
+        }
+    }
+
+    fn write(content_type: sql::ContentType) -> (&'static str, &'static str) {
+        match content_type {
+            sql::ContentType::Binary => ("", ""),
+            sql::ContentType::Json => ("convert_from(", ", 'UTF8')::jsonb"),
+        }
+    }
+}
+
+impl event::Lock for Postgres {
+    fn lock<'a, ID>(id: &'a ID) -> Option<QueryBuilder<'a, Self>>
+    where
+        ID: Encode<'a, Self> + Type<Self> + 'a,
+    {
+        // the lock is released when the transaction ends; a hash collision only serializes saves
+        let mut lock = QueryBuilder::new("SELECT pg_advisory_xact_lock(hashtextextended(CAST(");
+
+        lock.push_bind(id).push(" AS TEXT), 0))");
+
+        Some(lock)
+    }
+}
+
+impl event::Notify for Postgres {
+    fn notify<'a, ID>(
+        channel: &'a str,
+        id: &'a ID,
+        version: Version,
+    ) -> Option<QueryBuilder<'a, Self>>
+    where
+        ID: Encode<'a, Self> + Type<Self> + 'a,
+    {
+        // the payload is formatted as <version>:<id>, which is delivered when the save commits
+        let mut notify = QueryBuilder::new("SELECT pg_notify(");
+
+        notify
+            .push_bind(channel)
+            .push(", ")
+            .push_bind(format!("{}:", u64::from(version)))
+            .push(" || CAST(")
+            .push_bind(id)
+            .push(" AS TEXT))");
+
+        Some(notify)
+    }
+}
+
+sql_id_type!(
+    Postgres,
+    i16 => "SMALLINT",
+    i32 => "INTEGER",
+    i64 => "BIGINT",
+    Uuid => "UUID",
+    String => "VARCHAR(50)",
+);
+
+impl projection::Upsert for Postgres {
+    fn on_conflict(table: &sql::Ident, columns: &[&str]) -> String {
+        let mut clause =
+            String::from("ON CONFLICT (id) DO UPDATE SET checkpoint = EXCLUDED.checkpoint");
+
+        for column in columns {
+            let column = sql::Ident::unqualified(*column).quote();
+            let _ = write!(clause, ", {column} = EXCLUDED.{column}");
+        }
+
+        let _ = write!(
+            clause,
+            " WHERE {}.checkpoint <= EXCLUDED.checkpoint",
+            table.quote()
+        );
+        clause
+    }
+}
+
 impl<'a, ID> snapshot::Prune<'a, ID, Postgres> for Postgres
 where
     ID: Encode<'a, Postgres> + Type<Postgres>,
 {
     fn prune(
         table: &'a sql::Ident<'a>,
+        columns: &'a sql::Columns,
         id: &'a ID,
         clock: &'a dyn Clock,
         retention: &'a Retention,
@@ -27,9 +124,11 @@ where
         let mut delete = QueryBuilder::new("WITH s2 AS (");
 
         delete
-            .push("SELECT id, version FROM ")
+            .push(format!("SELECT {}, {} FROM ", columns.id, columns.version))
             .push(table.quote())
-            .push("WHERE id = ")
+            .push(" WHERE ")
+            .push(&columns.id)
+            .push(" = ")
             .push_bind(id);
 
         // SAFETY: unwrap is allowed here as before epoch is a bug in the clock
@@ -37,24 +136,33 @@ where
             if let Some(age) = retention.age {
                 let taken_on = (clock.now() - age).duration_since(UNIX_EPOCH).unwrap();
                 delete
-                    .push(" AND taken_on >= ")
+                    .push(" AND ")
+                    .push(&columns.taken_on)
+                    .push(" >= ")
                     .push_bind(taken_on.as_secs() as i64);
             }
 
             delete
-                .push(" ORDER BY taken_on DESC OFFSET ")
+                .push(" ORDER BY ")
+                .push(&columns.taken_on)
+                .push(" DESC OFFSET ")
                 .push_bind(count as i16);
         } else if let Some(age) = retention.age {
             let taken_on = (clock.now() - age).duration_since(UNIX_EPOCH).unwrap();
             delete
-                .push(" AND taken_on <= ")
+                .push(" AND ")
+                .push(&columns.taken_on)
+                .push(" <= ")
                 .push_bind(taken_on.as_secs() as i64);
         }
 
         delete
             .push(") DELETE FROM ")
             .push(table.quote())
-            .push(" s1 USING s2 WHERE s1.id = s2.id AND s1.version = s2.version;");
+            .push(format!(
+                " s1 USING s2 WHERE s1.{0} = s2.{0} AND s1.{1} = s2.{1};",
+                columns.id, columns.version
+            ));
 
         delete
     }
@@ -66,6 +174,9 @@ pub type EventStore<ID> = event::SqlStore<ID, Postgres>;
 /// Represents a Postgres [snapshot store](snapshot::SqlStore).
 pub type SnapshotStore<ID> = snapshot::SqlStore<ID, Postgres>;
 
+/// Represents a Postgres [projection sink](projection::SqlSink).
+pub type ProjectionSink = projection::SqlSink<Postgres>;
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "migrate")] {
         mod migration;
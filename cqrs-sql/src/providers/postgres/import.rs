@@ -0,0 +1,275 @@
+use super::EventStore;
+use crate::{
+    event::Lock,
+    sql::{Columns, ContentType, Partitioning},
+    SqlVersion, SqlVersionPart,
+};
+use cqrs::{message::Schema, Version};
+use futures::{Stream, StreamExt};
+use sqlx::{
+    encode::IsNull,
+    postgres::{PgArgumentBuffer, PgConnection},
+    Encode, Postgres, QueryBuilder, Row, Type,
+};
+use std::{collections::HashMap, error::Error, fmt::Debug, hash::Hash, pin::pin, time::SystemTime};
+use thiserror::Error;
+
+// signature, flags and header extension length
+const HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+const TRAILER: &[u8] = &(-1i16).to_be_bytes();
+const FIELDS: i16 = 8;
+const NULL: i32 = -1;
+const JSONB_VERSION: u8 = 1;
+const BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Represents a historical event to [import](EventStore::import).
+#[derive(Clone, Debug)]
+pub struct HistoricalEvent<ID> {
+    id: ID,
+    schema: Schema,
+    content: Vec<u8>,
+    stored_on: SystemTime,
+    correlation_id: Option<String>,
+}
+
+impl<ID> HistoricalEvent<ID> {
+    /// Initializes a new [HistoricalEvent].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the stream the event belongs to
+    /// * `schema` - the [schema](Schema) of the event
+    /// * `content` - the encoded event content
+    /// * `stored_on` - the [date](SystemTime) the event was originally stored on
+    pub fn new(id: ID, schema: Schema, content: Vec<u8>, stored_on: SystemTime) -> Self {
+        Self {
+            id,
+            schema,
+            content,
+            stored_on,
+            correlation_id: None,
+        }
+    }
+
+    /// Sets the correlation identifier of the event.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the correlation identifier
+    pub fn correlation_id<S: Into<String>>(mut self, value: S) -> Self {
+        self.correlation_id = Some(value.into());
+        self
+    }
+}
+
+/// Represents the possible import errors.
+#[derive(Error, Debug)]
+pub enum ImportError<ID: Debug + Send> {
+    /// Indicates the events for the specified identifier are not contiguous.
+    #[error("the events with identifier {0:?} are not contiguous")]
+    NotContiguous(ID),
+
+    /// Indicates the events for the specified identifier exceed the maximum
+    /// [version](Version).
+    #[error("the events with identifier {0:?} exceed the maximum version")]
+    VersionOverflow(ID),
+
+    /// Indicates the events for the specified identifier already exist.
+    #[error("the events with identifier {0:?} already exist")]
+    Exists(ID),
+
+    /// Indicates the specified identifier cannot be encoded.
+    #[error("the identifier {0:?} cannot be encoded")]
+    InvalidId(ID),
+
+    /// Indicates an unknown import [error](Error).
+    #[error(transparent)]
+    Unknown(#[from] Box<dyn Error + Send>),
+}
+
+impl<ID> EventStore<ID>
+where
+    ID: Clone + Debug + Eq + Hash + for<'db> Encode<'db, Postgres> + Send + Type<Postgres>,
+{
+    /// Imports a stream of historical events in bulk.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - the [stream](Stream) of [historical events](HistoricalEvent) to import
+    ///
+    /// # Returns
+    ///
+    /// The number of imported events.
+    ///
+    /// # Remarks
+    ///
+    /// The events are written using `COPY ... FROM STDIN (FORMAT binary)` in a single transaction,
+    /// which is significantly faster than saving each stream individually. The events of a stream
+    /// must be contiguous and in the order they occurred. Each event is assigned the next
+    /// [version](Version) in its stream, starting from the first version, so the imported streams
+    /// must not already exist. If any event cannot be imported, no events are imported.
+    ///
+    /// The events of a table [partitioned](Partitioning::Range) by range are locked and verified
+    /// after they are copied because its primary key cannot reject an existing stream.
+    pub async fn import<S>(&self, events: S) -> Result<u64, ImportError<ID>>
+    where
+        S: Stream<Item = HistoricalEvent<ID>> + Send,
+    {
+        let mut events = pin!(events);
+        let mut tx = self.pool.begin().await.map_err(box_err)?;
+        let mut copy = copy_in(&mut tx, &self.table.quote(), &self.columns).await?;
+        let mut imported = HashMap::new();
+        let mut current: Option<ID> = None;
+        let mut version = Version::default();
+        let mut buffer = Vec::with_capacity(BUFFER_SIZE);
+
+        buffer.extend_from_slice(HEADER);
+
+        while let Some(event) = events.next().await {
+            if current.as_ref() != Some(&event.id) {
+                if let Some(previous) = current.take() {
+                    imported.insert(previous, version.number());
+                }
+
+                if imported.contains_key(&event.id) {
+                    let _ = copy.abort("not contiguous").await;
+                    return Err(ImportError::NotContiguous(event.id));
+                }
+
+                current = Some(event.id.clone());
+                version = Version::default();
+            }
+
+            let next = version.increment(SqlVersionPart::Version);
+
+            if next.number() <= version.number() {
+                let _ = copy.abort("version overflow").await;
+                return Err(ImportError::VersionOverflow(event.id));
+            }
+
+            version = next;
+
+            if let Err(error) = write(&mut buffer, &event, version, self.content) {
+                let _ = copy.abort("invalid identifier").await;
+                return Err(error);
+            }
+
+            if buffer.len() >= BUFFER_SIZE {
+                copy.send(buffer.as_slice()).await.map_err(box_err)?;
+                buffer.clear();
+            }
+        }
+
+        buffer.extend_from_slice(TRAILER);
+        copy.send(buffer).await.map_err(box_err)?;
+
+        let count = copy.finish().await.map_err(box_err)?;
+
+        if let Some(previous) = current {
+            imported.insert(previous, version.number());
+        }
+
+        if let Partitioning::Range(_) = self.partitioning {
+            for (id, versions) in imported {
+                if let Some(mut lock) = Postgres::lock(&id) {
+                    let _ = lock.build().execute(&mut *tx).await.map_err(box_err)?;
+                }
+
+                let mut select = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM ");
+
+                select
+                    .push(self.table.quote())
+                    .push(" WHERE ")
+                    .push(&self.columns.id)
+                    .push(" = ")
+                    .push_bind(&id);
+
+                let row = select.build().fetch_one(&mut *tx).await.map_err(box_err)?;
+
+                if row.get::<i64, _>(0) != versions as i64 {
+                    return Err(ImportError::Exists(id));
+                }
+            }
+        }
+
+        tx.commit().await.map_err(box_err)?;
+        Ok(count)
+    }
+}
+
+async fn copy_in<'a, ID: Debug + Send>(
+    db: &'a mut PgConnection,
+    table: &str,
+    columns: &Columns,
+) -> Result<sqlx::postgres::PgCopyIn<&'a mut PgConnection>, ImportError<ID>> {
+    // historical events are not associated with a message so the column is not named, which
+    // allows tables without it to continue to work
+    let statement = format!(
+        "COPY {} ({}, {}, {}, {}, {}, {}, {}, {}) FROM STDIN (FORMAT binary)",
+        table,
+        columns.id,
+        columns.version,
+        columns.sequence,
+        columns.revision,
+        columns.stored_on,
+        columns.kind,
+        columns.content,
+        columns.correlation_id,
+    );
+
+    db.copy_in_raw(&statement).await.map_err(box_err)
+}
+
+fn write<ID>(
+    buffer: &mut Vec<u8>,
+    event: &HistoricalEvent<ID>,
+    version: Version,
+    content: ContentType,
+) -> Result<(), ImportError<ID>>
+where
+    ID: Clone + Debug + for<'db> Encode<'db, Postgres> + Send + Type<Postgres>,
+{
+    let mut id = PgArgumentBuffer::default();
+
+    match event.id.encode_by_ref(&mut id) {
+        Ok(IsNull::No) => {}
+        _ => return Err(ImportError::InvalidId(event.id.clone())),
+    }
+
+    buffer.extend_from_slice(&FIELDS.to_be_bytes());
+    field(buffer, &id);
+    field(buffer, &version.number().to_be_bytes());
+    field(buffer, &version.sequence().to_be_bytes());
+    field(buffer, &(event.schema.version() as i16).to_be_bytes());
+    field(buffer, &crate::to_secs(event.stored_on).to_be_bytes());
+    field(buffer, event.schema.kind().as_bytes());
+
+    match content {
+        ContentType::Binary => field(buffer, &event.content),
+        ContentType::Json => {
+            // the binary format of JSONB is a version number followed by the JSON text
+            buffer.extend_from_slice(&(event.content.len() as i32 + 1).to_be_bytes());
+            buffer.push(JSONB_VERSION);
+            buffer.extend_from_slice(&event.content);
+        }
+    }
+
+    if let Some(cid) = &event.correlation_id {
+        field(buffer, cid.as_bytes());
+    } else {
+        buffer.extend_from_slice(&NULL.to_be_bytes());
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn field(buffer: &mut Vec<u8>, value: &[u8]) {
+    buffer.extend_from_slice(&(value.len() as i32).to_be_bytes());
+    buffer.extend_from_slice(value);
+}
+
+#[inline]
+fn box_err<ID: Debug + Send>(error: sqlx::Error) -> ImportError<ID> {
+    ImportError::Unknown(Box::new(error))
+}
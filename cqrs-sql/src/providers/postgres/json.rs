@@ -0,0 +1,73 @@
+use super::EventStore;
+use crate::sql::{Condition, ContentType};
+use cqrs::event::{EventStream, Predicate, RawEventStream};
+use sqlx::{Decode, Encode, Postgres, Type};
+use std::fmt::Debug;
+
+impl<ID> EventStore<ID>
+where
+    ID: Clone
+        + Debug
+        + for<'db> Encode<'db, Postgres>
+        + for<'db> Decode<'db, Postgres>
+        + Send
+        + Sync
+        + Type<Postgres>
+        + 'static,
+{
+    /// Loads the [events](cqrs::event::Event) whose content matches a JSON path predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - the optional [predicate](Predicate) used to filter events
+    /// * `path` - the JSON path predicate the event content must match
+    ///
+    /// # Remarks
+    ///
+    /// The JSON path must be a predicate check expression, such as `$.total > 1000`, which is
+    /// evaluated using the `@@` operator. Events whose content does not produce a match, including
+    /// content without the specified path, are excluded. The events must be encoded as JSON. The
+    /// content is converted to `JSONB` when it is stored as bytes; storing the content as
+    /// [JSON](ContentType::Json) allows the predicate to be evaluated, and indexed, natively.
+    /// [Snapshots](cqrs::snapshot::Snapshot) are not loaded because they are not filtered.
+    pub async fn load_matching<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        path: &str,
+    ) -> EventStream<'a, ID> {
+        self.stream_from_pool(predicate, Some(self.json_path(path)))
+            .await
+    }
+
+    /// Loads the raw events whose content matches a JSON path predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - the optional [predicate](Predicate) used to filter events
+    /// * `path` - the JSON path predicate the event content must match
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [load_matching](Self::load_matching) for the supported JSON path predicates.
+    pub async fn load_raw_matching<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        path: &str,
+    ) -> RawEventStream<'a, ID> {
+        self.stream_raw(predicate, Some(self.json_path(path))).await
+    }
+
+    fn json_path(&self, path: &str) -> Condition {
+        let column = &self.columns.content;
+        let json = match self.content {
+            ContentType::Binary => format!("convert_from({column}, 'UTF8')::jsonb"),
+            ContentType::Json => column.to_string(),
+        };
+
+        Condition {
+            prefix: format!("{json} @@ CAST("),
+            value: path.to_owned(),
+            suffix: " AS jsonpath)",
+        }
+    }
+}
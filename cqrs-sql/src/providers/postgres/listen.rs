@@ -0,0 +1,102 @@
+use super::EventStore;
+use crate::BoxErr;
+use async_stream::try_stream;
+use cqrs::{event::StoreError, Version};
+use futures::{Stream, StreamExt};
+use sqlx::{postgres::PgListener, Decode, Encode, Postgres, Type};
+use std::{error::Error, fmt::Debug, pin::Pin, str::FromStr};
+
+/// Represents a [notification](Notification) [stream](Stream).
+pub type NotificationStream<ID> =
+    Pin<Box<dyn Stream<Item = Result<Notification<ID>, StoreError<ID>>> + Send>>;
+
+/// Represents a notification that events were saved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notification<ID> {
+    id: ID,
+    version: Version,
+}
+
+impl<ID> Notification<ID> {
+    /// Gets the identifier of the saved events.
+    pub fn id(&self) -> &ID {
+        &self.id
+    }
+
+    /// Gets the new [version](Version) of the saved events.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+}
+
+impl<ID> EventStore<ID>
+where
+    ID: Clone
+        + Debug
+        + FromStr
+        + for<'db> Encode<'db, Postgres>
+        + for<'db> Decode<'db, Postgres>
+        + Send
+        + Sync
+        + Type<Postgres>
+        + 'static,
+    <ID as FromStr>::Err: Error + Send + Sync + 'static,
+{
+    /// Listens for notifications that events were saved.
+    ///
+    /// # Remarks
+    ///
+    /// The store must be configured with a notification channel; otherwise,
+    /// [StoreError::Unsupported] is returned. The stream holds a dedicated connection, which is
+    /// reconnected if it is lost, and yields a [notification](Notification) for each committed
+    /// save. Payloads on the channel that are not notifications from a store are skipped.
+    /// Notifications are not durable. A save committed while the listener is disconnected is
+    /// not received, so a subscriber should load the events after its last known
+    /// [version](Version) rather than rely on each notification being delivered.
+    pub async fn listen(&self) -> Result<NotificationStream<ID>, StoreError<ID>> {
+        let Some(channel) = self.notify else {
+            return Err(StoreError::Unsupported);
+        };
+        let mut listener = PgListener::connect_with(&self.pool).await.box_err()?;
+
+        listener.listen(channel).await.box_err()?;
+
+        Ok(Box::pin(try_stream! {
+            let mut notifications = listener.into_stream();
+
+            while let Some(notification) = notifications.next().await {
+                let notification = notification.box_err()?;
+
+                // the channel may be shared with other publishers so a payload that cannot be
+                // parsed is not for this store and must not end the subscription
+                if let Ok(notification) = parse(notification.payload()) {
+                    yield notification;
+                }
+            }
+        }))
+    }
+}
+
+fn parse<ID>(payload: &str) -> Result<Notification<ID>, sqlx::Error>
+where
+    ID: FromStr,
+    <ID as FromStr>::Err: Error + Send + Sync + 'static,
+{
+    // the payload is formatted as <version>:<id>
+    let Some((version, id)) = payload.split_once(':') else {
+        return Err(sqlx::Error::Decode(
+            format!("invalid notification '{payload}'").into(),
+        ));
+    };
+    let version = version
+        .parse::<u64>()
+        .map_err(|error| sqlx::Error::Decode(error.into()))?;
+    let id = id
+        .parse()
+        .map_err(|error: ID::Err| sqlx::Error::Decode(error.into()))?;
+
+    Ok(Notification {
+        id,
+        version: Version::from(version),
+    })
+}
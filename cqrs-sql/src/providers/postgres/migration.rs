@@ -1,63 +1,74 @@
+use super::partition::range_partitions;
+use crate::migrate::{index_name, migrations, Script};
 use crate::SqlStoreMigrator;
 use crate::{
     postgres,
-    sql::{Ident, IdentPart::Schema},
+    sql::{
+        Columns,
+        ContentType::{self, Binary},
+        Ident,
+        IdentPart::Schema,
+        Partitioning, SqlIdType,
+    },
 };
-use sqlx::{
-    migrate::{Migration, MigrationType::Simple},
-    Postgres,
-};
-use std::any::type_name;
-use std::borrow::Cow;
-use std::mem::size_of;
+use sqlx::{migrate::Migration, Postgres};
 
 /// Represents a Postgres [migrator](SqlStoreMigrator).
 pub type Migrator = SqlStoreMigrator<Postgres>;
 
-impl<ID> From<&postgres::EventStore<ID>> for Migration {
+const EVENTS: &[Script] = &[
+    Script {
+        description: "events table",
+        sql: events_table,
+    },
+    Script {
+        description: "events message identifier",
+        sql: events_message_id,
+    },
+    Script {
+        description: "events secondary indexes",
+        sql: events_indexes,
+    },
+];
+
+const SNAPSHOTS: &[Script] = &[Script {
+    description: "snapshots table",
+    sql: snapshots_table,
+}];
+
+impl<ID: SqlIdType<Postgres>> From<&postgres::EventStore<ID>> for Vec<Migration> {
     fn from(value: &postgres::EventStore<ID>) -> Self {
-        Self::new(
-            1,
-            Cow::Owned(format!("'{}' events table.", value.table.name())),
-            Simple,
-            Cow::Owned(events_table(&value.table, db_type::<ID>())),
-            false,
+        migrations(
+            &value.table,
+            &value.columns,
+            ID::sql_type(),
+            value.content,
+            value.partitioning,
+            EVENTS,
         )
     }
 }
 
-impl<ID> From<&postgres::SnapshotStore<ID>> for Migration {
+impl<ID: SqlIdType<Postgres>> From<&postgres::SnapshotStore<ID>> for Vec<Migration> {
     fn from(value: &postgres::SnapshotStore<ID>) -> Self {
-        Self::new(
-            1,
-            Cow::Owned(format!("'{}' snapshots table.", value.table.name())),
-            Simple,
-            Cow::Owned(snapshots_table(&value.table, db_type::<ID>())),
-            false,
+        migrations(
+            &value.table,
+            &value.columns,
+            ID::sql_type(),
+            Binary,
+            Partitioning::None,
+            SNAPSHOTS,
         )
     }
 }
 
-#[inline]
-fn db_type<ID>() -> &'static str {
-    let name = type_name::<ID>();
-
-    match name {
-        "i8" | "u8" | "i16" | "u16" => "SMALLINT",
-        "i32" | "u32" => "INTEGER",
-        "i64" | "u64" => "BIGINT",
-        "isize" | "usize" => match size_of::<isize>() {
-            4 => "INTEGER",
-            8 => "BIGINT",
-            _ => unreachable!(),
-        },
-        "uuid::Uuid" => "UUID",
-        "alloc::string::String" => "VARCHAR(50)",
-        _ => panic!("type '{}' is not a supported database type", name),
-    }
-}
-
-fn events_table(table: &Ident, db_type: &str) -> String {
+fn events_table(
+    table: &Ident,
+    columns: &Columns,
+    db_type: &str,
+    content_type: ContentType,
+    partitioning: Partitioning,
+) -> String {
     let mut sql = String::new();
 
     if let Some(schema) = table.quote_part(Schema) {
@@ -69,23 +80,148 @@ fn events_table(table: &Ident, db_type: &str) -> String {
     sql.push_str("CREATE TABLE IF NOT EXISTS ");
     sql.push_str(&table.quote());
     sql.push('(');
-    sql.push_str("id ");
+    sql.push_str(&columns.id);
+    sql.push(' ');
     sql.push_str(db_type);
     sql.push_str(" NOT NULL, ");
-    sql.push_str("version INTEGER NOT NULL, ");
-    sql.push_str("sequence SMALLINT NOT NULL, ");
-    sql.push_str("revision SMALLINT NOT NULL, ");
-    sql.push_str("stored_on BIGINT NOT NULL, ");
-    sql.push_str("type VARCHAR(128) NOT NULL, ");
-    sql.push_str("content BYTEA NOT NULL, ");
-    sql.push_str("correlation_id VARCHAR(50) DEFAULT NULL, ");
-    sql.push_str("PRIMARY KEY(id, version, sequence)");
+    sql.push_str(&columns.version);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.sequence);
+    sql.push_str(" SMALLINT NOT NULL, ");
+    sql.push_str(&columns.revision);
+    sql.push_str(" SMALLINT NOT NULL, ");
+    sql.push_str(&columns.stored_on);
+    sql.push_str(" BIGINT NOT NULL, ");
+    sql.push_str(&columns.kind);
+    sql.push_str(" VARCHAR(128) NOT NULL, ");
+    sql.push_str(&columns.content);
+    sql.push_str(match content_type {
+        ContentType::Binary => " BYTEA NOT NULL, ",
+        ContentType::Json => " JSONB NOT NULL, ",
+    });
+    sql.push_str(&columns.correlation_id);
+    sql.push_str(" VARCHAR(50) DEFAULT NULL, ");
+    sql.push_str(&format!(
+        "PRIMARY KEY({}, {}, {}",
+        columns.id, columns.version, columns.sequence
+    ));
+
+    // the primary key of a partitioned table must include the partition key
+    match partitioning {
+        Partitioning::None => sql.push_str("));"),
+        Partitioning::Hash(partitions) => {
+            sql.push_str(&format!(")) PARTITION BY HASH ({});", columns.id));
+
+            for remainder in 0..partitions {
+                let name = format!("{}_{remainder}", table.name());
+                let partition = match table.quote_part(Schema) {
+                    Some(schema) => format!("{schema}.{}", Ident::unqualified(&name).quote()),
+                    None => Ident::unqualified(&name).quote().into_owned(),
+                };
+
+                sql.push_str(&format!(
+                    "\nCREATE TABLE IF NOT EXISTS {partition} PARTITION OF {} \
+                     FOR VALUES WITH (MODULUS {partitions}, REMAINDER {remainder});",
+                    table.quote()
+                ));
+            }
+        }
+        Partitioning::Range(interval) => {
+            sql.push_str(&format!(
+                ", {0})) PARTITION BY RANGE ({0});\n",
+                columns.stored_on
+            ));
+            sql.push_str(&range_partitions(table, interval, 1));
+
+            // events outside of the created ranges, such as historical imports, are stored in the
+            // default partition
+            let name = format!("{}_default", table.name());
+            let partition = match table.quote_part(Schema) {
+                Some(schema) => format!("{schema}.{}", Ident::unqualified(&name).quote()),
+                None => Ident::unqualified(&name).quote().into_owned(),
+            };
+
+            sql.push_str(&format!(
+                "\nCREATE TABLE IF NOT EXISTS {partition} PARTITION OF {} DEFAULT;",
+                table.quote()
+            ));
+        }
+    }
+
+    sql
+}
+
+fn events_message_id(
+    table: &Ident,
+    columns: &Columns,
+    _db_type: &str,
+    _content_type: ContentType,
+    partitioning: Partitioning,
+) -> String {
+    let table_name = table.quote();
+    let mut sql = String::new();
+
+    sql.push_str("ALTER TABLE ");
+    sql.push_str(&table_name);
+    sql.push_str(" ADD COLUMN IF NOT EXISTS ");
+    sql.push_str(&columns.message_id);
+    sql.push_str(" VARCHAR(50) DEFAULT NULL;\n");
+    sql.push_str("CREATE UNIQUE INDEX IF NOT EXISTS ");
+    sql.push_str(&index_name("ux", table, "message_id"));
+    sql.push_str(" ON ");
+    sql.push_str(&table_name);
+    sql.push_str(&format!("({}, {}", columns.id, columns.message_id));
+
+    // a unique index on a partitioned table must include the partition key
+    if let Partitioning::Range(_) = partitioning {
+        sql.push_str(", ");
+        sql.push_str(&columns.stored_on);
+    }
+
     sql.push_str(");");
 
     sql
 }
 
-fn snapshots_table(table: &Ident, db_type: &str) -> String {
+fn events_indexes(
+    table: &Ident,
+    columns: &Columns,
+    _db_type: &str,
+    content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
+    let table_name = table.quote();
+    let mut sql = String::new();
+
+    sql.push_str("CREATE INDEX IF NOT EXISTS ");
+    sql.push_str(&index_name("ix", table, "stored_on"));
+    sql.push_str(" ON ");
+    sql.push_str(&table_name);
+    sql.push_str(&format!("({});\n", columns.stored_on));
+    sql.push_str("CREATE INDEX IF NOT EXISTS ");
+    sql.push_str(&index_name("ix", table, "type_revision"));
+    sql.push_str(" ON ");
+    sql.push_str(&table_name);
+    sql.push_str(&format!("({}, {});", columns.kind, columns.revision));
+
+    if content_type == ContentType::Json {
+        sql.push_str("\nCREATE INDEX IF NOT EXISTS ");
+        sql.push_str(&index_name("ix", table, "content"));
+        sql.push_str(" ON ");
+        sql.push_str(&table_name);
+        sql.push_str(&format!(" USING GIN ({} jsonb_path_ops);", columns.content));
+    }
+
+    sql
+}
+
+fn snapshots_table(
+    table: &Ident,
+    columns: &Columns,
+    db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
     let mut sql = String::new();
 
     if let Some(schema) = table.quote_part(Schema) {
@@ -97,16 +233,23 @@ fn snapshots_table(table: &Ident, db_type: &str) -> String {
     sql.push_str("CREATE TABLE IF NOT EXISTS ");
     sql.push_str(&table.quote());
     sql.push('(');
-    sql.push_str("id ");
+    sql.push_str(&columns.id);
+    sql.push(' ');
     sql.push_str(db_type);
     sql.push_str(" NOT NULL, ");
-    sql.push_str("version INTEGER NOT NULL, ");
-    sql.push_str("revision SMALLINT NOT NULL, ");
-    sql.push_str("taken_on BIGINT NOT NULL, ");
-    sql.push_str("type VARCHAR(128) NOT NULL, ");
-    sql.push_str("content BYTEA NOT NULL, ");
-    sql.push_str("correlation_id VARCHAR(50) DEFAULT NULL, ");
-    sql.push_str("PRIMARY KEY(id, version)");
+    sql.push_str(&columns.version);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.revision);
+    sql.push_str(" SMALLINT NOT NULL, ");
+    sql.push_str(&columns.taken_on);
+    sql.push_str(" BIGINT NOT NULL, ");
+    sql.push_str(&columns.kind);
+    sql.push_str(" VARCHAR(128) NOT NULL, ");
+    sql.push_str(&columns.content);
+    sql.push_str(" BYTEA NOT NULL, ");
+    sql.push_str(&columns.correlation_id);
+    sql.push_str(" VARCHAR(50) DEFAULT NULL, ");
+    sql.push_str(&format!("PRIMARY KEY({}, {})", columns.id, columns.version));
     sql.push_str(");");
 
     sql
@@ -0,0 +1,80 @@
+use super::EventStore;
+use crate::{
+    sql::{Ident, IdentPart::Schema, Interval, Partitioning},
+    BoxErr,
+};
+use cqrs::event::StoreError;
+use sqlx::Executor;
+use std::fmt::Debug;
+
+impl<ID> EventStore<ID>
+where
+    ID: Debug + Send,
+{
+    /// Creates the partitions for the current and next number of intervals.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - the number of future [intervals](Interval) to create partitions for
+    ///
+    /// # Remarks
+    ///
+    /// The store must be [partitioned](Partitioning::Range) by the date the events are stored on;
+    /// otherwise, [StoreError::Unsupported] is returned. Events stored beyond the last partition
+    /// are stored in the default partition, which prevents a partition from being created for
+    /// their interval later, so this function should be run periodically, such as daily, with
+    /// enough partitions to cover the time until it is run again. Existing partitions are left
+    /// unchanged.
+    pub async fn create_partitions(&self, count: u16) -> Result<(), StoreError<ID>> {
+        let Partitioning::Range(interval) = self.partitioning else {
+            return Err(StoreError::Unsupported);
+        };
+        let mut db = self.pool.acquire().await.box_err()?;
+        let sql = range_partitions(&self.table, interval, count);
+
+        let _ = db.execute(sql.as_str()).await.box_err()?;
+        Ok(())
+    }
+}
+
+/// Creates the SQL that adds the range partitions of a table for the current and next intervals.
+///
+/// # Arguments
+///
+/// * `table` - the [identifier](Ident) of the partitioned table
+/// * `interval` - the [interval](Interval) of each partition
+/// * `count` - the number of future intervals to create partitions for
+///
+/// # Remarks
+///
+/// The partition boundaries are computed by the database so that the SQL is the same each time
+/// it is created. Each partition is named after its table and the UTC date it starts on; for
+/// example, `events_20240101`.
+pub(crate) fn range_partitions(table: &Ident, interval: Interval, count: u16) -> String {
+    let parent = table.quote().replace('\'', "''");
+    let schema = table
+        .quote_part(Schema)
+        .map(|schema| format!("{}.", schema.replace('\'', "''")))
+        .unwrap_or_default();
+    let name = table.name().replace('\'', "''");
+    let field = interval.name();
+
+    format!(
+        "DO $$\n\
+         DECLARE\n    \
+             lower TIMESTAMP := date_trunc('{field}', now() AT TIME ZONE 'UTC');\n    \
+             upper TIMESTAMP;\n\
+         BEGIN\n    \
+             FOR i IN 0..{count} LOOP\n        \
+                 upper := lower + INTERVAL '1 {field}';\n        \
+                 EXECUTE format(\n            \
+                     'CREATE TABLE IF NOT EXISTS {schema}%I PARTITION OF {parent} \
+                      FOR VALUES FROM (%s) TO (%s)',\n            \
+                     '{name}_' || to_char(lower, 'YYYYMMDD'),\n            \
+                     EXTRACT(EPOCH FROM lower)::BIGINT,\n            \
+                     EXTRACT(EPOCH FROM upper)::BIGINT);\n        \
+                 lower := upper;\n    \
+             END LOOP;\n\
+         END $$;"
+    )
+}
@@ -1,39 +1,92 @@
 mod command;
 mod event_store;
+mod projection_sink;
 mod snapshot_store;
 
 pub use event_store::EventStore;
+pub use projection_sink::ProjectionSink;
 pub use snapshot_store::SnapshotStore;
 
-use crate::{snapshot, sql};
+use crate::{event, projection, snapshot, sql};
 use cqrs::{snapshot::Retention, Clock};
 use sqlx::{Encode, QueryBuilder, Sqlite, Type};
-use std::time::UNIX_EPOCH;
+use std::{fmt::Write, time::UNIX_EPOCH};
+use uuid::Uuid;
 
 impl snapshot::Upsert for Sqlite {
-    fn on_conflict() -> &'static str {
-        concat!(
-            "ON CONFLICT (id, version) DO UPDATE SET ",
-            "taken_on = EXCLUDED.taken_on, ",
-            "revision = EXCLUDED.revision, ",
-            "type = EXCLUDED.type"
+    fn on_conflict(columns: &sql::Columns) -> String {
+        format!(
+            "ON CONFLICT ({0}, {1}) DO UPDATE SET \
+             {2} = EXCLUDED.{2}, {3} = EXCLUDED.{3}, {4} = EXCLUDED.{4}",
+            columns.id, columns.version, columns.taken_on, columns.revision, columns.kind
         )
     }
 }
 
+impl sql::BindLimit for Sqlite {
+    fn max_binds() -> usize {
+        // the default SQLITE_MAX_VARIABLE_NUMBER since SQLite 3.32.0
+        32_766
+    }
+}
+
+impl sql::ContentFormat for Sqlite {}
+
+impl event::Lock for Sqlite {}
+
+impl event::Notify for Sqlite {}
+
+sql_id_type!(
+    Sqlite,
+    i8 => "INTEGER",
+    u8 => "INTEGER",
+    i16 => "INTEGER",
+    u16 => "INTEGER",
+    i32 => "INTEGER",
+    u32 => "INTEGER",
+    i64 => "INTEGER",
+    Uuid => "BLOB",
+    String => "TEXT",
+);
+
+impl projection::Upsert for Sqlite {
+    fn on_conflict(table: &sql::Ident, columns: &[&str]) -> String {
+        let mut clause =
+            String::from("ON CONFLICT (id) DO UPDATE SET checkpoint = EXCLUDED.checkpoint");
+
+        for column in columns {
+            let column = sql::Ident::unqualified(*column).quote();
+            let _ = write!(clause, ", {column} = EXCLUDED.{column}");
+        }
+
+        let _ = write!(
+            clause,
+            " WHERE {}.checkpoint <= EXCLUDED.checkpoint",
+            table.quote()
+        );
+        clause
+    }
+}
+
 impl<'a, ID> snapshot::Prune<'a, ID, Sqlite> for Sqlite
 where
     ID: Encode<'a, Sqlite> + Type<Sqlite>,
 {
     fn prune(
         table: &'a sql::Ident<'a>,
+        columns: &'a sql::Columns,
         id: &'a ID,
         clock: &'a dyn Clock,
         retention: &'a Retention,
     ) -> sqlx::QueryBuilder<'a, Sqlite> {
         let mut delete = QueryBuilder::new("DELETE FROM ");
 
-        delete.push(table.quote()).push("WHERE id = ").push_bind(id);
+        delete
+            .push(table.quote())
+            .push(" WHERE ")
+            .push(&columns.id)
+            .push(" = ")
+            .push_bind(id);
 
         // SAFETY: unwrap is allowed here as before epoch is a bug in the clock
         // LIMIT must be specified so use the largest possible value
@@ -41,17 +94,23 @@ where
             if let Some(age) = retention.age {
                 let taken_on = (clock.now() - age).duration_since(UNIX_EPOCH).unwrap();
                 delete
-                    .push(" AND taken_on >= ")
+                    .push(" AND ")
+                    .push(&columns.taken_on)
+                    .push(" >= ")
                     .push_bind(taken_on.as_secs() as i64);
             }
 
             delete
-                .push(" ORDER BY taken_on DESC LIMIT 2305843009213693951 OFFSET ")
+                .push(" ORDER BY ")
+                .push(&columns.taken_on)
+                .push(" DESC LIMIT 2305843009213693951 OFFSET ")
                 .push_bind(count as i16);
         } else if let Some(age) = retention.age {
             let taken_on = (clock.now() - age).duration_since(UNIX_EPOCH).unwrap();
             delete
-                .push(" AND taken_on <= ")
+                .push(" AND ")
+                .push(&columns.taken_on)
+                .push(" <= ")
                 .push_bind(taken_on.as_secs() as i64);
         }
 
@@ -5,19 +5,22 @@ use std::{error::Error, fmt::Debug};
 
 pub async fn insert_transacted<'a, ID>(
     table: &'a sql::Ident<'a>,
-    row: &'a sql::Row<ID>,
+    columns: &sql::Columns,
+    rows: &'a [sql::Row<ID>],
     tx: &'a mut Transaction<'_, Sqlite>,
 ) -> Result<(), StoreError<ID>>
 where
     ID: Clone + Debug + for<'db> Encode<'db, Sqlite> + Send + Type<Sqlite>,
 {
-    let mut insert = event::command::insert::<ID, Sqlite>(table, row);
+    let mut insert = event::command::insert::<ID, Sqlite>(table, columns, sql::ContentType::Binary, rows);
 
     if let Err(error) = insert.build().execute(&mut **tx).await {
         if let sqlx::Error::Database(error) = &error
             && error.is_unique_violation()
         {
-            Err(StoreError::Conflict(row.id.clone(), row.version as u32))
+            // the rows are the chunk that failed, whose first row has the conflicting version
+            let first = &rows[0];
+            Err(StoreError::Conflict(first.id.clone(), first.version as u32))
         } else {
             Err(StoreError::Unknown(Box::new(error) as Box<dyn Error + Send>))
         }
@@ -28,13 +31,14 @@ where
 
 pub async fn ensure_not_deleted<'a, ID>(
     table: &'a sql::Ident<'a>,
+    columns: &sql::Columns,
     previous: &'a sql::Row<ID>,
     tx: &'a mut Transaction<'_, Sqlite>,
 ) -> Result<(), StoreError<ID>>
 where
     ID: Clone + Debug + for<'db> Encode<'db, Sqlite> + Send + Type<Sqlite>,
 {
-    let exists: bool = event::command::exists(table, previous)
+    let exists: bool = event::command::exists(table, columns, previous)
         .build_query_scalar()
         .fetch_one(&mut **tx)
         .await
@@ -1,25 +1,36 @@
 use super::command as cmd;
 use crate::{
     BoxErr, SqlStoreBuilder, SqlVersion, SqlVersionPart,
-    event::{command, get_snapshot, select_version},
-    new_version,
-    sql::{self, Context, Ident, IntoRows},
+    event::{Pages, command, get_snapshot, select_after, select_version},
+    from_secs, new_version,
+    sql::{self, Columns, ContentType::Binary, Context, Ident, IntoRows},
 };
 use async_stream::try_stream;
 use async_trait::async_trait;
 use cqrs::{
-    Clock, Range, Version,
-    event::{Event, EventStream, IdStream, Predicate, Store, StoreError, StoreOptions},
-    message::{Saved, Schema},
+    Clock, Concurrency, Range, Version,
+    event::{
+        Append, Event, EventStream, ExpectedVersion, Head, IdPage, IdStream, Predicate, RawEvent,
+        RawEventStream, Store, StoreError, StoreOptions,
+    },
+    message::{Descriptor, Saved, Schema},
 };
 use futures::stream;
-use sqlx::Sqlite;
 use sqlx::{Connection, Decode, Encode, Pool, Row, Type};
-use std::{error::Error, fmt::Debug, ops::Bound::Unbounded, sync::Arc, time::SystemTime};
+use sqlx::{Sqlite, SqliteConnection, Transaction};
+use std::{
+    error::Error,
+    fmt::Debug,
+    ops::{Bound::Unbounded, DerefMut},
+    sync::Arc,
+    time::SystemTime,
+};
 
 /// Represents a SQLite [event store](Store).
 pub struct EventStore<ID> {
     table: String,
+    pub(crate) columns: Columns,
+    pub(crate) page_size: Option<usize>,
     pub(crate) pool: Pool<Sqlite>,
     options: StoreOptions<ID>,
 }
@@ -35,6 +46,8 @@ impl<ID> EventStore<ID> {
     pub fn new(table: String, pool: Pool<Sqlite>, options: StoreOptions<ID>) -> Self {
         Self {
             table,
+            columns: Columns::default(),
+            page_size: None,
             pool,
             options,
         }
@@ -50,8 +63,7 @@ impl<ID> EventStore<ID> {
     }
 }
 
-#[async_trait]
-impl<ID> Store<ID> for EventStore<ID>
+impl<ID> EventStore<ID>
 where
     ID: Clone
         + Debug
@@ -62,38 +74,257 @@ where
         + Type<Sqlite>
         + 'static,
 {
-    fn clock(&self) -> Arc<dyn Clock> {
-        (&self.options).into()
+    /// Loads a sequence of [events](Event) using an existing transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - the [transaction](Transaction) to load the events in
+    /// * `predicate` - the optional [predicate](Predicate) used to filter events
+    ///
+    /// # Remarks
+    ///
+    /// The transaction is owned by the caller, which allows events to be read in the same unit of
+    /// work as other application data. [Snapshots](cqrs::snapshot::Snapshot), if any, are loaded
+    /// from the configured [snapshot store](cqrs::snapshot::Store) outside of the transaction.
+    pub async fn load_in<'a>(
+        &self,
+        tx: &'a mut Transaction<'_, Sqlite>,
+        predicate: Option<&'a Predicate<'a, ID>>,
+    ) -> EventStream<'a, ID> {
+        self.stream(&mut **tx, predicate).await
     }
 
-    async fn ids(&self, stored_on: Range<SystemTime>) -> IdStream<ID> {
-        let mut db = match self.pool.acquire().await.box_err() {
-            Ok(db) => db,
-            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+    /// Saves a collection of events using an existing transaction and returns the new
+    /// [version](Version), if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - the [transaction](Transaction) to save the events in
+    /// * `id` - the identifier of the events to save
+    /// * `expected_version` - the current, expected [version](Version)
+    /// * `events` - the list of [events](Event) to save
+    ///
+    /// # Remarks
+    ///
+    /// The transaction is owned by the caller, which allows events to be saved in the same unit of
+    /// work as other application data. The events are not visible to others until the caller
+    /// commits the transaction. Since the transaction cannot be retried by the store, a concurrency
+    /// [conflict](StoreError::Conflict) is always returned to the caller, who is expected to roll
+    /// back the transaction.
+    pub async fn save_in(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<ID>> {
+        let append = Append::new(id.clone(), expected_version, events);
+        self.write(&append, self.options.concurrency(), tx).await
+    }
+
+    async fn append(
+        &self,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: Option<&str>,
+        concurrency: Concurrency,
+    ) -> Result<Version, StoreError<ID>> {
+        if events.is_empty() {
+            return Ok(expected_version);
+        }
+
+        if message_id.is_some() {
+            let mut db = self.pool.acquire().await.box_err()?;
+
+            if let Some(version) = self.find(id, message_id, &mut db).await? {
+                return Ok(version);
+            }
+        }
+
+        let mut version = if expected_version != Version::default()
+            && let Some(mask) = self.options.mask()
+        {
+            expected_version.unmask(mask)
+        } else {
+            expected_version
         };
-        let name = self.table.clone();
 
-        Box::pin(try_stream! {
-            let table = Ident::unqualified(&name);
-            let mut query = command::select_id(table, stored_on);
-            let rows = query.build().fetch(&mut *db);
+        if version.invalid() {
+            return Err(StoreError::InvalidVersion);
+        }
 
-            for await row in rows {
-                yield row.box_err()?.get::<ID, _>(0);
+        let table = self.table();
+
+        loop {
+            version = version.increment(SqlVersionPart::Version);
+
+            let context = Context {
+                id: id.clone(),
+                version,
+                clock: self.options.clock(),
+                transcoder: self.options.transcoder(),
+                message_id,
+            };
+            let mut rows = events.into_rows(context);
+            let batch = rows.by_ref().collect::<Result<Vec<_>, _>>()?;
+            let Some(first) = batch.first() else {
+                return Ok(expected_version);
+            };
+            let previous = if self.options.delete().supported() {
+                first.previous()
+            } else {
+                None
+            };
+            let chunks = batch.chunks(command::rows_per_insert::<Sqlite>());
+            let mut db = self.pool.acquire().await.box_err()?;
+
+            if previous.is_none() && chunks.len() == 1 {
+                let mut insert = command::insert(&table, &self.columns, Binary, &batch);
+
+                if let Err(error) = insert.build().execute(&mut *db).await {
+                    if let sqlx::Error::Database(error) = &error
+                        && error.is_unique_violation()
+                    {
+                        if let Some(version) = self.find(id, message_id, &mut db).await? {
+                            return Ok(version);
+                        } else if concurrency.enforced() {
+                            return Err(StoreError::Conflict(id.clone(), first.version as u32));
+                        } else {
+                            continue;
+                        }
+                    }
+                    return Err(StoreError::Unknown(Box::new(error) as Box<dyn Error + Send>));
+                }
+            } else {
+                let mut tx = db.begin().await.box_err()?;
+
+                if let Some(previous) = previous {
+                    cmd::ensure_not_deleted(&table, &self.columns, &previous, &mut tx).await?;
+                }
+
+                let mut result = Ok(());
+
+                for chunk in chunks {
+                    result = cmd::insert_transacted(&table, &self.columns, chunk, &mut tx).await;
+
+                    if result.is_err() {
+                        break;
+                    }
+                }
+
+                // a conflict can occur in any chunk, such as when a concurrent save has a
+                // larger batch, and the transaction is retried the same way regardless
+                if let Err(error @ StoreError::Conflict(_, _)) = result {
+                    tx.rollback().await.box_err()?;
+
+                    // the message may have been saved concurrently
+                    if let Some(version) = self.find(id, message_id, &mut db).await? {
+                        return Ok(version);
+                    } else if concurrency.enforced() {
+                        return Err(error);
+                    } else {
+                        continue;
+                    }
+                }
+
+                result?;
+
+                tx.commit().await.box_err()?;
             }
-        })
+
+            version = rows.version();
+            break;
+        }
+
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
+        }
+
+        Ok(version)
     }
 
-    async fn load<'a>(&self, predicate: Option<&'a Predicate<'a, ID>>) -> EventStream<'a, ID> {
-        let mut db = match self.pool.acquire().await.box_err() {
-            Ok(db) => db,
-            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+    async fn write(
+        &self,
+        append: &Append<'_, ID>,
+        concurrency: Concurrency,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<Version, StoreError<ID>> {
+        let id = append.id();
+        let events = append.events();
+
+        if events.is_empty() {
+            return Ok(append.expected_version());
+        }
+
+        let mut version = if append.expected_version() != Version::default()
+            && let Some(mask) = self.options.mask()
+        {
+            append.expected_version().unmask(mask)
+        } else {
+            append.expected_version()
+        };
+
+        if version.invalid() {
+            return Err(StoreError::InvalidVersion);
+        }
+
+        let table = self.table();
+
+        if !concurrency.enforced() {
+            let mut select = command::select_max_version(&table, &self.columns, id);
+            let row = select.build().fetch_one(&mut **tx).await.box_err()?;
+
+            if let Some(current) = row.get::<Option<i32>, _>(0)
+                && current > version.number()
+            {
+                version = new_version(current, 0);
+            }
+        }
+
+        let context = Context {
+            id: id.clone(),
+            version: version.increment(SqlVersionPart::Version),
+            clock: self.options.clock(),
+            transcoder: self.options.transcoder(),
+            message_id: None,
         };
+        let mut rows = events.into_rows(context);
+        let batch = rows.by_ref().collect::<Result<Vec<_>, _>>()?;
+
+        if self.options.delete().supported()
+            && let Some(previous) = batch.first().and_then(sql::Row::previous)
+        {
+            cmd::ensure_not_deleted(&table, &self.columns, &previous, tx).await?;
+        }
+
+        for chunk in batch.chunks(command::rows_per_insert::<Sqlite>()) {
+            cmd::insert_transacted(&table, &self.columns, chunk, tx).await?;
+        }
+
+        version = rows.version();
+
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
+        }
+
+        Ok(version)
+    }
+
+    async fn stream<'a, C>(
+        &self,
+        mut db: C,
+        predicate: Option<&'a Predicate<'a, ID>>,
+    ) -> EventStream<'a, ID>
+    where
+        C: DerefMut<Target = SqliteConnection> + Send + 'a,
+    {
         let snapshot = match get_snapshot(self.options.snapshots(), predicate).await {
             Ok(snapshot) => snapshot,
             Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::from(error))])),
         };
         let name = self.table.clone();
+        let columns = self.columns.clone();
         let options = self.options.clone();
 
         Box::pin(try_stream! {
@@ -109,13 +340,17 @@ where
                 version = select_version(snapshot.as_ref(), filter, options.mask());
 
                 if let Some(snapshot) = snapshot {
-                    let event = options.transcoder().decode(&snapshot.schema, &snapshot.content)?;
-                    yield Saved::new(event, snapshot.version);
+                    let (schema, version) = (&snapshot.schema, snapshot.version);
+
+                    if let Some(event) = options.decode(schema, version, &snapshot.content)? {
+                        yield Saved::new(event, version);
+                    }
                 }
             }
 
             let table = Ident::unqualified(&name);
-            let mut query = command::select(table, predicate, version);
+            let after = predicate.and_then(|filter| select_after(filter, options.mask()));
+            let mut query = command::select(table, &columns, Binary, predicate, version, after, None);
             let rows = query.build().fetch(&mut *db);
 
             for await result in rows {
@@ -125,7 +360,6 @@ where
                     row.get::<i16, _>(REVISION) as u8,
                 );
                 let content = row.get::<&[u8], _>(CONTENT);
-                let event = options.transcoder().decode(&schema, content)?;
                 let mut version = new_version(
                     row.get::<i32, _>(VERSION),
                     row.get::<i16, _>(SEQUENCE),
@@ -135,134 +369,488 @@ where
                     version = version.mask(mask);
                 }
 
-                yield Saved::new(event, version);
+                if let Some(event) = options.decode(&schema, version, content)? {
+                    yield Saved::new(event, version);
+                }
             }
         })
     }
 
-    async fn save(
+    async fn stream_pages<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        size: usize,
+    ) -> EventStream<'a, ID> {
+        let snapshot = match get_snapshot(self.options.snapshots(), predicate).await {
+            Ok(snapshot) => snapshot,
+            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::from(error))])),
+        };
+        let pool = self.pool.clone();
+        let name = self.table.clone();
+        let columns = self.columns.clone();
+        let options = self.options.clone();
+
+        Box::pin(try_stream! {
+            const TYPE: usize = 0;
+            const REVISION: usize = 1;
+            const VERSION: usize = 2;
+            const SEQUENCE: usize = 3;
+            const CONTENT: usize = 4;
+
+            let mut version = Unbounded;
+
+            if let Some(filter) = predicate {
+                version = select_version(snapshot.as_ref(), filter, options.mask());
+
+                if let Some(snapshot) = snapshot {
+                    let (schema, version) = (&snapshot.schema, snapshot.version);
+
+                    if let Some(event) = options.decode(schema, version, &snapshot.content)? {
+                        yield Saved::new(event, version);
+                    }
+                }
+            }
+
+            let after = predicate.and_then(|filter| select_after(filter, options.mask()));
+            let mut pages = Pages::new(size, predicate, after);
+
+            loop {
+                let rows = {
+                    let Some(page) = pages.next(predicate) else {
+                        break;
+                    };
+                    let mut db = pool.acquire().await.box_err()?;
+                    let table = Ident::unqualified(&name);
+                    let after = pages.after();
+                    let mut query =
+                        command::select_raw(table, &columns, Binary, Some(&page), version, after, None);
+
+                    // the connection is released before the rows are yielded
+                    query.build().fetch_all(&mut *db).await.box_err()?
+                };
+
+                pages.advance(&rows);
+
+                for row in rows {
+                    let schema = Schema::new(
+                        row.get::<&str, _>(TYPE),
+                        row.get::<i16, _>(REVISION) as u8,
+                    );
+                    let content = row.get::<&[u8], _>(CONTENT);
+                    let mut version = new_version(
+                        row.get::<i32, _>(VERSION),
+                        row.get::<i16, _>(SEQUENCE),
+                    );
+
+                    if let Some(mask) = options.mask() {
+                        version = version.mask(mask);
+                    }
+
+                    if let Some(event) = options.decode(&schema, version, content)? {
+                        yield Saved::new(event, version);
+                    }
+                }
+            }
+        })
+    }
+
+    fn stream_raw_pages<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+        size: usize,
+    ) -> RawEventStream<'a, ID> {
+        let pool = self.pool.clone();
+        let name = self.table.clone();
+        let columns = self.columns.clone();
+        let options = self.options.clone();
+
+        Box::pin(try_stream! {
+            const TYPE: usize = 0;
+            const REVISION: usize = 1;
+            const VERSION: usize = 2;
+            const SEQUENCE: usize = 3;
+            const CONTENT: usize = 4;
+            const IDENTIFIER: usize = 5;
+            const STORED_ON: usize = 6;
+
+            let version = if let Some(filter) = predicate {
+                select_version(None, filter, options.mask())
+            } else {
+                Unbounded
+            };
+            let after = predicate.and_then(|filter| select_after(filter, options.mask()));
+            let mut pages = Pages::new(size, predicate, after);
+
+            loop {
+                let rows = {
+                    let Some(page) = pages.next(predicate) else {
+                        break;
+                    };
+                    let mut db = pool.acquire().await.box_err()?;
+                    let table = Ident::unqualified(&name);
+                    let after = pages.after();
+                    let mut query =
+                        command::select_raw(table, &columns, Binary, Some(&page), version, after, None);
+
+                    // the connection is released before the rows are yielded
+                    query.build().fetch_all(&mut *db).await.box_err()?
+                };
+
+                pages.advance(&rows);
+
+                for row in rows {
+                    let schema = Schema::new(
+                        row.get::<&str, _>(TYPE),
+                        row.get::<i16, _>(REVISION) as u8,
+                    );
+                    let content = row.get::<&[u8], _>(CONTENT).to_vec();
+                    let mut version = new_version(
+                        row.get::<i32, _>(VERSION),
+                        row.get::<i16, _>(SEQUENCE),
+                    );
+
+                    if let Some(mask) = options.mask() {
+                        version = version.mask(mask);
+                    }
+
+                    yield RawEvent::new(
+                        row.get::<ID, _>(IDENTIFIER),
+                        from_secs(row.get::<i64, _>(STORED_ON)),
+                        Descriptor::new(schema, version, content),
+                    );
+                }
+            }
+        })
+    }
+
+    fn ids_in_pages(&self, stored_on: Range<SystemTime>, size: usize) -> IdStream<ID> {
+        let pool = self.pool.clone();
+        let name = self.table.clone();
+        let columns = self.columns.clone();
+        let size = size.max(1);
+
+        Box::pin(try_stream! {
+            let mut after = None;
+
+            loop {
+                let ids = {
+                    let mut db = pool.acquire().await.box_err()?;
+                    let table = Ident::unqualified(&name);
+                    let mut query =
+                        command::select_id_page(table, &columns, stored_on.clone(), size, after.as_ref());
+                    let rows = query.build().fetch_all(&mut *db).await.box_err()?;
+
+                    rows.iter().map(|row| row.get::<ID, _>(0)).collect::<Vec<_>>()
+                };
+                let last = ids.len() < size;
+
+                after = ids.last().cloned();
+
+                for id in ids {
+                    yield id;
+                }
+
+                if last {
+                    break;
+                }
+            }
+        })
+    }
+
+    async fn find(
         &self,
         id: &ID,
-        expected_version: Version,
-        events: &[Box<dyn Event>],
-    ) -> Result<Version, StoreError<ID>> {
-        if events.is_empty() {
-            return Ok(expected_version);
+        message_id: Option<&str>,
+        db: &mut SqliteConnection,
+    ) -> Result<Option<Version>, StoreError<ID>> {
+        if let Some(message_id) = message_id {
+            let table = self.table();
+            let mut select = command::select_message(&table, &self.columns, id, message_id);
+
+            if let Some(row) = select.build().fetch_optional(&mut *db).await.box_err()? {
+                let mut version = new_version(row.get::<i32, _>(0), row.get::<i16, _>(1));
+
+                if let Some(mask) = self.options.mask() {
+                    version = version.mask(mask);
+                }
+
+                return Ok(Some(version));
+            }
         }
 
-        let mut version = if expected_version != Version::default()
-            && let Some(mask) = self.options.mask()
-        {
-            expected_version.unmask(mask)
-        } else {
-            expected_version
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl<ID> Store<ID> for EventStore<ID>
+where
+    ID: Clone
+        + Debug
+        + for<'db> Encode<'db, Sqlite>
+        + for<'db> Decode<'db, Sqlite>
+        + Send
+        + Sync
+        + Type<Sqlite>
+        + 'static,
+{
+    fn clock(&self) -> Arc<dyn Clock> {
+        (&self.options).into()
+    }
+
+    async fn ids(&self, stored_on: Range<SystemTime>) -> IdStream<ID> {
+        if let Some(size) = self.page_size {
+            return self.ids_in_pages(stored_on, size);
+        }
+
+        let mut db = match self.pool.acquire().await.box_err() {
+            Ok(db) => db,
+            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
         };
+        let name = self.table.clone();
+        let columns = self.columns.clone();
 
-        if version.invalid() {
-            return Err(StoreError::InvalidVersion);
+        Box::pin(try_stream! {
+            let table = Ident::unqualified(&name);
+            let mut query = command::select_id(table, &columns, stored_on);
+            let rows = query.build().fetch(&mut *db);
+
+            for await row in rows {
+                yield row.box_err()?.get::<ID, _>(0);
+            }
+        })
+    }
+
+    async fn ids_page(
+        &self,
+        stored_on: Range<SystemTime>,
+        limit: usize,
+        after: Option<&ID>,
+    ) -> Result<IdPage<ID>, StoreError<ID>> {
+        let mut db = self.pool.acquire().await.box_err()?;
+        let mut query =
+            command::select_id_page(self.table(), &self.columns, stored_on, limit, after);
+        let rows = query.build().fetch_all(&mut *db).await.box_err()?;
+        let ids = rows.iter().map(|row| row.get::<ID, _>(0)).collect();
+
+        Ok(IdPage::of(ids, limit))
+    }
+
+    async fn load<'a>(&self, predicate: Option<&'a Predicate<'a, ID>>) -> EventStream<'a, ID> {
+        if let Some(size) = self.page_size {
+            return self.stream_pages(predicate, size).await;
         }
 
-        let table = self.table();
+        let db = match self.pool.acquire().await.box_err() {
+            Ok(db) => db,
+            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+        };
 
-        loop {
-            version = version.increment(SqlVersionPart::Version);
+        self.stream(db, predicate).await
+    }
 
-            let context = Context {
-                id: id.clone(),
-                version,
-                clock: self.options.clock(),
-                transcoder: self.options.transcoder(),
-            };
-            let mut rows = events.into_rows(context);
-            let first = if let Some(row) = rows.next() {
-                row?
+    async fn load_raw<'a>(
+        &self,
+        predicate: Option<&'a Predicate<'a, ID>>,
+    ) -> RawEventStream<'a, ID> {
+        if let Some(size) = self.page_size {
+            return self.stream_raw_pages(predicate, size);
+        }
+
+        let mut db = match self.pool.acquire().await.box_err() {
+            Ok(db) => db,
+            Err(error) => return Box::pin(stream::iter(vec![Err(StoreError::Unknown(error))])),
+        };
+        let name = self.table.clone();
+        let columns = self.columns.clone();
+        let options = self.options.clone();
+
+        Box::pin(try_stream! {
+            const TYPE: usize = 0;
+            const REVISION: usize = 1;
+            const VERSION: usize = 2;
+            const SEQUENCE: usize = 3;
+            const CONTENT: usize = 4;
+            const IDENTIFIER: usize = 5;
+            const STORED_ON: usize = 6;
+
+            let version = if let Some(filter) = predicate {
+                select_version(None, filter, options.mask())
             } else {
-                return Ok(expected_version);
+                Unbounded
             };
 
-            let mut db = self.pool.acquire().await.box_err()?;
+            let table = Ident::unqualified(&name);
+            let after = predicate.and_then(|filter| select_after(filter, options.mask()));
+            let mut query = command::select_raw(table, &columns, Binary, predicate, version, after, None);
+            let rows = query.build().fetch(&mut *db);
 
-            if let Some(second) = rows.next() {
-                let mut tx = db.begin().await.box_err()?;
-                let second = second?;
+            for await result in rows {
+                let row = result.box_err()?;
+                let schema = Schema::new(
+                    row.get::<&str, _>(TYPE),
+                    row.get::<i16, _>(REVISION) as u8,
+                );
+                let content = row.get::<&[u8], _>(CONTENT).to_vec();
+                let mut version = new_version(
+                    row.get::<i32, _>(VERSION),
+                    row.get::<i16, _>(SEQUENCE),
+                );
 
-                if self.options.delete().supported()
-                    && let Some(previous) = first.previous()
-                {
-                    cmd::ensure_not_deleted(&table, &previous, &mut tx).await?;
+                if let Some(mask) = options.mask() {
+                    version = version.mask(mask);
                 }
 
-                let result = cmd::insert_transacted(&table, &first, &mut tx).await;
+                yield RawEvent::new(
+                    row.get::<ID, _>(IDENTIFIER),
+                    from_secs(row.get::<i64, _>(STORED_ON)),
+                    Descriptor::new(schema, version, content),
+                );
+            }
+        })
+    }
 
-                // if the first row succeeds or fails, the rest will fail or succeed
-                if matches!(result, Err(StoreError::Conflict(_, _))) {
-                    if self.options.concurrency().enforced() {
-                        result?;
-                    } else {
-                        continue;
-                    }
-                }
+    async fn head(&self, id: &ID) -> Result<Head<ID>, StoreError<ID>> {
+        const VERSION: usize = 0;
+        const SEQUENCE: usize = 1;
+        const STORED_ON: usize = 2;
+        const COUNT: usize = 3;
+        const FIRST_STORED_ON: usize = 4;
 
-                cmd::insert_transacted(&table, &second, &mut tx).await?;
+        let table = self.table();
+        let mut db = self.pool.acquire().await.box_err()?;
+        let mut select = command::select_head(&table, &self.columns, id);
+        let Some(row) = select.build().fetch_optional(&mut *db).await.box_err()? else {
+            return Ok(Head::empty(id.clone()));
+        };
+        let mut version = new_version(row.get::<i32, _>(VERSION), row.get::<i16, _>(SEQUENCE));
 
-                #[allow(clippy::while_let_on_iterator)] // false positive; rows.version() used below
-                while let Some(row) = rows.next() {
-                    let row = row?;
-                    cmd::insert_transacted(&table, &row, &mut tx).await?;
-                }
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
+        }
 
-                tx.commit().await.box_err()?;
-            } else {
-                let mut execute = true;
+        Ok(Head::new(
+            id.clone(),
+            version,
+            row.get::<i64, _>(COUNT) as u64,
+            from_secs(row.get::<i64, _>(FIRST_STORED_ON)),
+            from_secs(row.get::<i64, _>(STORED_ON)),
+        ))
+    }
 
-                if self.options.delete().supported()
-                    && let Some(previous) = first.previous()
-                {
-                    let mut tx = db.begin().await.box_err()?;
+    async fn save(
+        &self,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<ID>> {
+        let concurrency = self.options.concurrency();
+        self.append(id, expected_version, events, None, concurrency)
+            .await
+    }
 
-                    cmd::ensure_not_deleted(&table, &previous, &mut tx).await?;
+    async fn save_expecting(
+        &self,
+        id: &ID,
+        expected: ExpectedVersion,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<ID>> {
+        let expected_version = match expected {
+            ExpectedVersion::Exact(version) => {
+                let table = self.table();
+                let mut db = self.pool.acquire().await.box_err()?;
+                let mut tx = db.begin().await.box_err()?;
+                let mut select = command::select_last_version(&table, &self.columns, id);
+
+                // the current version is read in the same transaction that appends the events so
+                // that an expectation ahead of the stream cannot leave a gap in its versions
+                let Some(row) = select.build().fetch_optional(&mut *tx).await.box_err()? else {
+                    return Err(StoreError::NotFound(id.clone()));
+                };
+                let current = new_version(row.get::<i32, _>(0), row.get::<i16, _>(1));
+                let expected_version = if let Some(mask) = self.options.mask() {
+                    version.unmask(mask)
+                } else {
+                    version
+                };
+
+                if current != expected_version {
+                    let version = expected_version.number() as u32;
+                    return Err(StoreError::Conflict(id.clone(), version));
+                }
 
-                    let result = cmd::insert_transacted(&table, &first, &mut tx).await;
+                let version = self
+                    .write(
+                        &Append::new(id.clone(), version, events),
+                        expected.concurrency(),
+                        &mut tx,
+                    )
+                    .await?;
 
-                    if matches!(result, Err(StoreError::Conflict(_, _))) {
-                        if self.options.concurrency().enforced() {
-                            result?;
-                        } else {
-                            continue;
-                        }
+                tx.commit().await.box_err()?;
+                return Ok(version);
+            }
+            ExpectedVersion::NoStream => Version::default(),
+            ExpectedVersion::Any | ExpectedVersion::StreamExists => {
+                let table = self.table();
+                let mut db = self.pool.acquire().await.box_err()?;
+                let mut select = command::select_max_version(&table, &self.columns, id);
+                let row = select.build().fetch_one(&mut *db).await.box_err()?;
+
+                if let Some(current) = row.get::<Option<i32>, _>(0) {
+                    let mut version = new_version(current, 0);
+
+                    if let Some(mask) = self.options.mask() {
+                        version = version.mask(mask);
                     }
 
-                    tx.commit().await.box_err()?;
-                    execute = false;
+                    version
+                } else if expected == ExpectedVersion::StreamExists {
+                    return Err(StoreError::NotFound(id.clone()));
+                } else {
+                    Version::default()
                 }
+            }
+        };
 
-                if execute {
-                    let mut insert = command::insert(&table, &first);
-
-                    if let Err(error) = insert.build().execute(&mut *db).await {
-                        if let sqlx::Error::Database(error) = &error
-                            && error.is_unique_violation()
-                        {
-                            if self.options.concurrency().enforced() {
-                                return Err(StoreError::Conflict(id.clone(), first.version as u32));
-                            } else {
-                                continue;
-                            }
-                        }
-                        return Err(StoreError::Unknown(Box::new(error) as Box<dyn Error + Send>));
+        self.append(id, expected_version, events, None, expected.concurrency())
+            .await
+    }
+
+    async fn save_once(
+        &self,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: &str,
+    ) -> Result<Version, StoreError<ID>> {
+        let concurrency = self.options.concurrency();
+        self.append(id, expected_version, events, Some(message_id), concurrency)
+            .await
+    }
+
+    async fn save_all(&self, appends: &[Append<'_, ID>]) -> Result<Vec<Version>, StoreError<ID>> {
+        let concurrency = self.options.concurrency();
+        let mut db = self.pool.acquire().await.box_err()?;
+
+        'retry: loop {
+            let mut tx = db.begin().await.box_err()?;
+            let mut versions = Vec::with_capacity(appends.len());
+
+            for append in appends {
+                match self.write(append, concurrency, &mut tx).await {
+                    Ok(version) => versions.push(version),
+                    Err(StoreError::Conflict(_, _)) if !concurrency.enforced() => {
+                        tx.rollback().await.box_err()?;
+                        continue 'retry;
                     }
+                    Err(error) => return Err(error),
                 }
             }
 
-            version = rows.version();
-            break;
+            tx.commit().await.box_err()?;
+            return Ok(versions);
         }
-
-        if let Some(mask) = self.options.mask() {
-            version = version.mask(mask);
-        }
-
-        Ok(version)
     }
 
     async fn delete(&self, id: &ID) -> Result<(), StoreError<ID>> {
@@ -281,10 +869,97 @@ where
         let mut db = self.pool.acquire().await.box_err()?;
         let mut tx = db.begin().await.box_err()?;
         let table = self.table();
-        let mut delete = sql::command::delete(&table, id);
+        let mut delete = sql::command::delete(&table, &self.columns, id);
         let _ = delete.build().execute(&mut *tx).await.box_err()?;
 
         tx.commit().await.box_err()?;
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "migrate"))]
+mod tests {
+    use super::*;
+    use crate::{SqlStoreMigration, event::command::ROWS_PER_INSERT, sqlite::Migrator};
+    use cqrs::{event, transcode};
+    use serde::{Deserialize, Serialize};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[transcode(with = Json)]
+    mod events {
+        #[event]
+        #[derive(Default, Deserialize, Serialize)]
+        pub struct Credited {
+            amount: f32,
+        }
+    }
+
+    async fn new_store() -> Result<EventStore<String>, Box<dyn Error>> {
+        let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+        let store: EventStore<String> = EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_4b6d8f0a2c4e46a8b0d2f4a6c8e0b2d4")
+            .transcoder(transcoder::events())
+            .try_into()?;
+        let migrator = Migrator::new();
+
+        migrator.add(SqlStoreMigration::with_pool(&store, sqlite));
+        migrator.run().await?;
+
+        Ok(store)
+    }
+
+    fn credits(count: usize) -> Vec<Box<dyn Event>> {
+        (0..count)
+            .map(|_| Box::new(Credited { amount: 1.0 }) as Box<dyn Event>)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn save_should_insert_batch_in_chunks() -> Result<(), Box<dyn Error>> {
+        // arrange
+        ROWS_PER_INSERT.set(Some(2));
+
+        let store = new_store().await?;
+        let id = String::from("42");
+
+        // act
+        let version = store.save(&id, Version::default(), &credits(5)).await?;
+
+        // assert
+        let head = store.head(&id).await?;
+
+        assert_eq!(head.version(), version);
+        assert_eq!(head.count(), 5);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_should_retry_batch_when_later_chunk_conflicts() -> Result<(), Box<dyn Error>> {
+        // arrange
+        ROWS_PER_INSERT.set(Some(2));
+
+        let store = new_store().await?;
+        let id = String::from("42");
+
+        store.save(&id, Version::default(), &credits(3)).await?;
+
+        // leave only the last event so that the first chunk of the next batch at the same
+        // version succeeds and the second chunk conflicts
+        let table = store.table().quote();
+        let delete =
+            format!("DELETE FROM {table} WHERE sequence < (SELECT MAX(sequence) FROM {table})");
+
+        sqlx::query(&delete).execute(&store.pool).await?;
+
+        // act
+        let version = store.save(&id, Version::default(), &credits(3)).await?;
+
+        // assert
+        let head = store.head(&id).await?;
+
+        assert_eq!(head.version(), version);
+        assert_eq!(head.count(), 4);
+        Ok(())
+    }
+}
@@ -1,91 +1,180 @@
+use crate::migrate::{index_name, migrations, Script};
 use crate::SqlStoreMigrator;
-use crate::{sqlite, sql::Ident};
-use sqlx::{
-    migrate::{Migration, MigrationType::Simple},
-    Sqlite,
+use crate::{
+    sql::{
+        Columns,
+        ContentType::{self, Binary},
+        Ident, Partitioning, SqlIdType,
+    },
+    sqlite,
 };
-use std::any::type_name;
-use std::borrow::Cow;
+use sqlx::{migrate::Migration, Sqlite};
 
 /// Represents a SQLite [migrator](SqlStoreMigrator).
 pub type Migrator = SqlStoreMigrator<Sqlite>;
 
-impl<ID> From<&sqlite::EventStore<ID>> for Migration {
+const EVENTS: &[Script] = &[
+    Script {
+        description: "events table",
+        sql: events_table,
+    },
+    Script {
+        description: "events message identifier",
+        sql: events_message_id,
+    },
+    Script {
+        description: "events secondary indexes",
+        sql: events_indexes,
+    },
+];
+
+const SNAPSHOTS: &[Script] = &[Script {
+    description: "snapshots table",
+    sql: snapshots_table,
+}];
+
+impl<ID: SqlIdType<Sqlite>> From<&sqlite::EventStore<ID>> for Vec<Migration> {
     fn from(value: &sqlite::EventStore<ID>) -> Self {
-        Self::new(
-            1,
-            Cow::Owned(format!("'{}' events table.", value.table().name())),
-            Simple,
-            Cow::Owned(events_table(&value.table(), db_type::<ID>())),
-            false,
+        migrations(
+            &value.table(),
+            &value.columns,
+            ID::sql_type(),
+            Binary,
+            Partitioning::None,
+            EVENTS,
         )
     }
 }
 
-impl<ID> From<&sqlite::SnapshotStore<ID>> for Migration {
+impl<ID: SqlIdType<Sqlite>> From<&sqlite::SnapshotStore<ID>> for Vec<Migration> {
     fn from(value: &sqlite::SnapshotStore<ID>) -> Self {
-        Self::new(
-            1,
-            Cow::Owned(format!("'{}' snapshots table.", value.table().name())),
-            Simple,
-            Cow::Owned(snapshots_table(&value.table(), db_type::<ID>())),
-            false,
+        migrations(
+            &value.table(),
+            &value.columns,
+            ID::sql_type(),
+            Binary,
+            Partitioning::None,
+            SNAPSHOTS,
         )
     }
 }
 
-#[inline]
-fn db_type<ID>() -> &'static str {
-    let name = type_name::<ID>();
-
-    match name {
-        "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "isize" | "usize" => {
-            "INTEGER"
-        }
-        "uuid::Uuid" => "BLOB",
-        "alloc::string::String" => "TEXT",
-        _ => panic!("type '{}' is not a supported database type", name),
-    }
-}
-
-fn events_table(table: &Ident, db_type: &str) -> String {
+fn events_table(
+    table: &Ident,
+    columns: &Columns,
+    db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
     let mut sql = String::new();
 
     sql.push_str("CREATE TABLE IF NOT EXISTS ");
     sql.push_str(&table.quote());
     sql.push('(');
-    sql.push_str("id ");
+    sql.push_str(&columns.id);
+    sql.push(' ');
     sql.push_str(db_type);
     sql.push_str(" NOT NULL, ");
-    sql.push_str("version INTEGER NOT NULL, ");
-    sql.push_str("sequence INTEGER NOT NULL, ");
-    sql.push_str("revision INTEGER NOT NULL, ");
-    sql.push_str("stored_on INTEGER NOT NULL, ");
-    sql.push_str("type TEXT NOT NULL, ");
-    sql.push_str("content BLOB NOT NULL, ");
-    sql.push_str("correlation_id TEXT DEFAULT NULL, ");
-    sql.push_str("PRIMARY KEY(id, version, sequence)");
+    sql.push_str(&columns.version);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.sequence);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.revision);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.stored_on);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.kind);
+    sql.push_str(" TEXT NOT NULL, ");
+    sql.push_str(&columns.content);
+    sql.push_str(" BLOB NOT NULL, ");
+    sql.push_str(&columns.correlation_id);
+    sql.push_str(" TEXT DEFAULT NULL, ");
+    sql.push_str(&format!(
+        "PRIMARY KEY({}, {}, {})",
+        columns.id, columns.version, columns.sequence
+    ));
     sql.push_str(");");
 
     sql
 }
 
-fn snapshots_table(table: &Ident, db_type: &str) -> String {
+fn events_message_id(
+    table: &Ident,
+    columns: &Columns,
+    _db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
+    let table_name = table.quote();
+    let mut sql = String::new();
+
+    sql.push_str("ALTER TABLE ");
+    sql.push_str(&table_name);
+    sql.push_str(" ADD COLUMN ");
+    sql.push_str(&columns.message_id);
+    sql.push_str(" TEXT DEFAULT NULL;\n");
+    sql.push_str("CREATE UNIQUE INDEX IF NOT EXISTS ");
+    sql.push_str(&index_name("ux", table, "message_id"));
+    sql.push_str(" ON ");
+    sql.push_str(&table_name);
+    sql.push_str(&format!("({}, {});", columns.id, columns.message_id));
+
+    sql
+}
+
+fn events_indexes(
+    table: &Ident,
+    columns: &Columns,
+    _db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
+    let table_name = table.quote();
+    let mut sql = String::new();
+
+    sql.push_str("CREATE INDEX IF NOT EXISTS ");
+    sql.push_str(&index_name("ix", table, "stored_on"));
+    sql.push_str(" ON ");
+    sql.push_str(&table_name);
+    sql.push_str(&format!("({});\n", columns.stored_on));
+    sql.push_str("CREATE INDEX IF NOT EXISTS ");
+    sql.push_str(&index_name("ix", table, "type_revision"));
+    sql.push_str(" ON ");
+    sql.push_str(&table_name);
+    sql.push_str(&format!("({}, {});", columns.kind, columns.revision));
+
+    sql
+}
+
+fn snapshots_table(
+    table: &Ident,
+    columns: &Columns,
+    db_type: &str,
+    _content_type: ContentType,
+    _partitioning: Partitioning,
+) -> String {
     let mut sql = String::new();
 
     sql.push_str("CREATE TABLE IF NOT EXISTS ");
     sql.push_str(&table.quote());
     sql.push('(');
-    sql.push_str("id ");
+    sql.push_str(&columns.id);
+    sql.push(' ');
     sql.push_str(db_type);
     sql.push_str(" NOT NULL, ");
-    sql.push_str("version INTEGER NOT NULL, ");
-    sql.push_str("revision INTEGER NOT NULL, ");
-    sql.push_str("taken_on INTEGER NOT NULL, ");
-    sql.push_str("type TEXT NOT NULL, ");
-    sql.push_str("content BLOB NOT NULL, ");
-    sql.push_str("correlation_id TEXT DEFAULT NULL, ");
-    sql.push_str("PRIMARY KEY(id, version)");
+    sql.push_str(&columns.version);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.revision);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.taken_on);
+    sql.push_str(" INTEGER NOT NULL, ");
+    sql.push_str(&columns.kind);
+    sql.push_str(" TEXT NOT NULL, ");
+    sql.push_str(&columns.content);
+    sql.push_str(" BLOB NOT NULL, ");
+    sql.push_str(&columns.correlation_id);
+    sql.push_str(" TEXT DEFAULT NULL, ");
+    sql.push_str(&format!("PRIMARY KEY({}, {})", columns.id, columns.version));
     sql.push_str(");");
 
     sql
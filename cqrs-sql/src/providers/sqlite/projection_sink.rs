@@ -0,0 +1,79 @@
+use crate::{
+    BoxErr,
+    projection::{ReadModel, command},
+    sql::Ident,
+};
+use async_trait::async_trait;
+use cqrs::projection::Sink;
+use sqlx::{Encode, Pool, Sqlite, Type};
+use std::error::Error;
+
+/// Represents a SQLite [sink](Sink) that stores projections as [read models](ReadModel).
+///
+/// # Remarks
+///
+/// Each projection is upserted by its identifier along with its checkpoint. A projection with an
+/// earlier checkpoint than the stored read model is ignored, which makes writes idempotent.
+pub struct ProjectionSink {
+    table: String,
+    pool: Pool<Sqlite>,
+}
+
+impl ProjectionSink {
+    /// Initializes a new [ProjectionSink].
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - the read model table identifier
+    /// * `pool` - the underlying [connection pool](Pool)
+    ///
+    /// # Remarks
+    ///
+    /// The [connection pool](Pool) can be the same pool used by an event store.
+    pub fn new<S: Into<String>>(table: S, pool: Pool<Sqlite>) -> Self {
+        Self {
+            table: table.into(),
+            pool,
+        }
+    }
+
+    /// Gets the checkpoint of the read model stored for the specified identifier, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the read model
+    pub async fn checkpoint<ID>(&self, id: &ID) -> Result<Option<i64>, Box<dyn Error + Send>>
+    where
+        ID: for<'db> Encode<'db, Sqlite> + Send + Sync + Type<Sqlite>,
+    {
+        let mut db = self.pool.acquire().await.box_err()?;
+        let table = self.table();
+        let mut select = command::select_checkpoint(&table, id);
+
+        select
+            .build_query_scalar()
+            .fetch_optional(&mut *db)
+            .await
+            .box_err()
+    }
+
+    fn table(&self) -> Ident<'_> {
+        Ident::unqualified(&self.table)
+    }
+}
+
+#[async_trait]
+impl<ID, P> Sink<ID, P> for ProjectionSink
+where
+    ID: for<'db> Encode<'db, Sqlite> + Send + Sync + Type<Sqlite>,
+    P: ReadModel<Sqlite> + 'static,
+{
+    async fn write(&self, id: &ID, projection: P) -> Result<(), Box<dyn Error + Send>> {
+        let mut db = self.pool.acquire().await.box_err()?;
+        let table = self.table();
+        let mut upsert = command::upsert(&table, id, &projection);
+        let _ = upsert.build().execute(&mut *db).await.box_err()?;
+
+        Ok(())
+    }
+}
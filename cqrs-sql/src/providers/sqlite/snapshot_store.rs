@@ -1,7 +1,7 @@
 use crate::{
     BoxErr, SqlStoreBuilder, SqlVersion, new_version,
     snapshot::{Prune, command},
-    sql::{self, Ident},
+    sql::{self, Columns, Ident},
 };
 use async_trait::async_trait;
 use cqrs::{
@@ -17,6 +17,7 @@ use std::{fmt::Debug, marker::PhantomData};
 pub struct SnapshotStore<ID> {
     _id: PhantomData<ID>,
     table: String,
+    pub(crate) columns: Columns,
     pub(crate) pool: Pool<Sqlite>,
     options: StoreOptions,
 }
@@ -33,6 +34,7 @@ impl<ID> SnapshotStore<ID> {
         Self {
             _id: PhantomData,
             table,
+            columns: Columns::default(),
             pool,
             options,
         }
@@ -87,7 +89,7 @@ where
         const CONTENT: usize = 3;
 
         let mut db = self.pool.acquire().await.box_err()?;
-        let mut query = command::select(&self.table(), id, predicate, self.options.mask());
+        let mut query = command::select(&self.table(), &self.columns, id, predicate, self.options.mask());
         let mut rows = query.build().fetch(&mut *db);
 
         if let Some(result) = rows.next().await {
@@ -136,10 +138,11 @@ where
             revision: schema.version() as i16,
             content,
             correlation_id: None,
+            message_id: None,
         };
         let mut db = self.pool.acquire().await.box_err()?;
         let table = self.table();
-        let mut insert = command::insert(&table, &row);
+        let mut insert = command::insert(&table, &self.columns, &row);
         let _ = insert.build().execute(&mut *db).await.box_err()?;
 
         Ok(())
@@ -151,10 +154,10 @@ where
         let table = self.table();
 
         if let Some(retention) = retention {
-            let mut delete = Sqlite::prune(&table, id, self.options.clock(), retention);
+            let mut delete = Sqlite::prune(&table, &self.columns, id, self.options.clock(), retention);
             let _ = delete.build().execute(&mut *tx).await.box_err()?;
         } else {
-            let mut delete = sql::command::delete(&table, id);
+            let mut delete = sql::command::delete(&table, &self.columns, id);
             let _ = delete.build().execute(&mut *tx).await.box_err()?;
         }
 
@@ -1,7 +1,7 @@
 use super::Upsert;
 use crate::{
     SqlVersion,
-    sql::{self, greater_than},
+    sql::{self, Columns, greater_than},
 };
 use cqrs::{Mask, snapshot::Predicate};
 use sqlx::{Database, Encode, QueryBuilder, Type};
@@ -9,6 +9,7 @@ use std::fmt::Debug;
 
 pub fn select<'a, ID, DB>(
     table: &sql::Ident<'a>,
+    columns: &Columns,
     id: &'a ID,
     predicate: Option<&Predicate>,
     mask: Option<&(dyn Mask + 'static)>,
@@ -19,12 +20,17 @@ where
     i32: Debug + for<'db> Encode<'db, DB> + Send + Type<DB>,
     i64: Debug + for<'db> Encode<'db, DB> + Send + Type<DB>,
 {
-    let mut select = QueryBuilder::new("SELECT version, type, revision, content ");
+    let mut select = QueryBuilder::new(format!(
+        "SELECT {}, {}, {}, {} ",
+        columns.version, columns.kind, columns.revision, columns.content
+    ));
 
     select
         .push(" FROM ")
         .push(table.quote())
-        .push(" WHERE id = ")
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
         .push_bind(id);
 
     if let Some(predicate) = predicate {
@@ -34,7 +40,9 @@ where
             }
 
             select
-                .push(" AND version ")
+                .push(" AND ")
+                .push(&columns.version)
+                .push(' ')
                 .push(op)
                 .push(" ")
                 .push_bind(version.number());
@@ -43,20 +51,30 @@ where
         if let Some((since, op)) = greater_than(&predicate.since) {
             select
                 .push(" AND ")
+                .push(&columns.taken_on)
+                .push(' ')
                 .push(op)
                 .push(" ")
                 .push_bind(crate::to_secs(since))
-                .push(" ORDER BY taken_on");
+                .push(" ORDER BY ")
+                .push(&columns.taken_on);
         }
     } else {
-        select.push(" ORDER BY version DESC");
+        select
+            .push(" ORDER BY ")
+            .push(&columns.version)
+            .push(" DESC");
     }
 
     select.push(" LIMIT 1;");
     select
 }
 
-pub fn insert<'a, ID, DB>(table: &'a sql::Ident<'a>, row: &'a sql::Row<ID>) -> QueryBuilder<'a, DB>
+pub fn insert<'a, ID, DB>(
+    table: &'a sql::Ident<'a>,
+    columns: &Columns,
+    row: &'a sql::Row<ID>,
+) -> QueryBuilder<'a, DB>
 where
     DB: Database + Upsert,
     ID: Encode<'a, DB> + Send + Type<DB> + 'a,
@@ -70,7 +88,16 @@ where
 
     insert
         .push(table.quote())
-        .push(' ')
+        .push(format!(
+            " ({}, {}, {}, {}, {}, {}, {})",
+            columns.id,
+            columns.version,
+            columns.revision,
+            columns.taken_on,
+            columns.kind,
+            columns.content,
+            columns.correlation_id
+        ))
         .push(" VALUES (")
         .push_bind(&row.id)
         .push(", ")
@@ -91,6 +118,6 @@ where
         insert.push("NULL");
     }
 
-    insert.push(") ").push(DB::on_conflict()).push(';');
+    insert.push(") ").push(DB::on_conflict(columns)).push(';');
     insert
 }
@@ -9,11 +9,13 @@ pub trait Prune<'a, ID, DB: Database> {
     /// # Arguments
     ///
     /// * `table` - the table [identifier](sql::Ident)
+    /// * `columns` - the table [columns](sql::Columns)
     /// * `id` - the identifier of the snapshots to prune
     /// * `clock` - the current [clock](Clock)
     /// * `retention` - the [retention](Retention) policy to apply
     fn prune(
         table: &'a sql::Ident<'a>,
+        columns: &'a sql::Columns,
         id: &'a ID,
         clock: &'a dyn Clock,
         retention: &'a Retention,
@@ -1,7 +1,7 @@
 use super::{Prune, Upsert, command};
 use crate::{
     BoxErr, SqlStoreBuilder, SqlVersion, new_version,
-    sql::{self, Ident},
+    sql::{self, Columns, Ident},
 };
 use async_trait::async_trait;
 use cqrs::{
@@ -19,6 +19,7 @@ use std::{fmt::Debug, marker::PhantomData};
 pub struct SqlStore<ID, DB: Database> {
     _id: PhantomData<ID>,
     pub(crate) table: Ident<'static>,
+    pub(crate) columns: Columns,
     pub(crate) pool: Pool<DB>,
     options: StoreOptions,
 }
@@ -35,6 +36,7 @@ impl<ID, DB: Database> SqlStore<ID, DB> {
         Self {
             _id: PhantomData,
             table,
+            columns: Columns::default(),
             pool,
             options,
         }
@@ -95,7 +97,7 @@ where
         const CONTENT: usize = 3;
 
         let mut db = self.pool.acquire().await.box_err()?;
-        let mut query = command::select(&self.table, id, predicate, self.options.mask());
+        let mut query = command::select(&self.table, &self.columns, id, predicate, self.options.mask());
         let mut rows = query.build().fetch(&mut *db);
 
         if let Some(result) = rows.next().await {
@@ -144,9 +146,10 @@ where
             revision: schema.version() as i16,
             content,
             correlation_id: None,
+            message_id: None,
         };
         let mut db = self.pool.acquire().await.box_err()?;
-        let mut insert = command::insert(&self.table, &row);
+        let mut insert = command::insert(&self.table, &self.columns, &row);
         let _ = insert.build().execute(&mut *db).await.box_err()?;
 
         Ok(())
@@ -157,10 +160,10 @@ where
         let mut tx = db.begin().await.box_err()?;
 
         if let Some(retention) = retention {
-            let mut delete = DB::prune(&self.table, id, self.options.clock(), retention);
+            let mut delete = DB::prune(&self.table, &self.columns, id, self.options.clock(), retention);
             let _ = delete.build().execute(&mut *tx).await.box_err()?;
         } else {
-            let mut delete = sql::command::delete(&self.table, id);
+            let mut delete = sql::command::delete(&self.table, &self.columns, id);
             let _ = delete.build().execute(&mut *tx).await.box_err()?;
         }
 
@@ -1,5 +1,11 @@
+use crate::sql::Columns;
+
 /// Defines the behavior of a SQL upsert for snapshots.
 pub trait Upsert {
     /// Gets the appropriate SQL `ON CONFLICT` upsert clause.
-    fn on_conflict() -> &'static str;
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - the snapshot table [columns](Columns)
+    fn on_conflict(columns: &Columns) -> String;
 }
\ No newline at end of file
@@ -1,8 +1,19 @@
 pub(crate) mod command;
+mod columns;
+mod content;
+mod id_type;
 mod ident;
+mod limit;
+mod partition;
 mod row;
 
+pub use columns::Columns;
+pub use content::{ContentFormat, ContentType};
+pub use id_type::SqlIdType;
+pub(crate) use content::Condition;
 pub use ident::{Ident, IdentPart};
+pub use limit::BindLimit;
+pub use partition::{Interval, Partitioning};
 pub(crate) use row::{Context, IntoRows, Row};
 
 use std::ops::Bound::{self, Excluded, Included};
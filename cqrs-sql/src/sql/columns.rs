@@ -0,0 +1,82 @@
+use super::Ident;
+use std::borrow::Cow;
+
+/// Represents the mapping of the columns in a storage table.
+///
+/// # Remarks
+///
+/// The default column names match the tables created by the store migrations. A custom mapping
+/// allows a store to use an existing table whose columns have different names, provided the
+/// column types are compatible. Only the columns that apply to the type of store are used;
+/// for example, [taken_on](Self::taken_on) only applies to snapshots. A column name that is not
+/// a plain identifier, such as one with a space, is quoted when it is used in a statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Columns {
+    /// Gets or sets the name of the identifier column.
+    pub id: Cow<'static, str>,
+
+    /// Gets or sets the name of the version column.
+    pub version: Cow<'static, str>,
+
+    /// Gets or sets the name of the event sequence column.
+    pub sequence: Cow<'static, str>,
+
+    /// Gets or sets the name of the message type version column.
+    pub revision: Cow<'static, str>,
+
+    /// Gets or sets the name of the column for the date and time an event was stored.
+    pub stored_on: Cow<'static, str>,
+
+    /// Gets or sets the name of the column for the date and time a snapshot was taken.
+    pub taken_on: Cow<'static, str>,
+
+    /// Gets or sets the name of the message type column.
+    pub kind: Cow<'static, str>,
+
+    /// Gets or sets the name of the message content column.
+    pub content: Cow<'static, str>,
+
+    /// Gets or sets the name of the correlation identifier column.
+    pub correlation_id: Cow<'static, str>,
+
+    /// Gets or sets the name of the event message identifier column.
+    pub message_id: Cow<'static, str>,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            id: Cow::Borrowed("id"),
+            version: Cow::Borrowed("version"),
+            sequence: Cow::Borrowed("sequence"),
+            revision: Cow::Borrowed("revision"),
+            stored_on: Cow::Borrowed("stored_on"),
+            taken_on: Cow::Borrowed("taken_on"),
+            kind: Cow::Borrowed("type"),
+            content: Cow::Borrowed("content"),
+            correlation_id: Cow::Borrowed("correlation_id"),
+            message_id: Cow::Borrowed("message_id"),
+        }
+    }
+}
+
+impl Columns {
+    /// Returns the columns with each name quoted, if necessary, for use in a statement.
+    pub(crate) fn quote(&self) -> Self {
+        let quote =
+            |name: &Cow<'static, str>| Cow::Owned(Ident::unqualified(name).quote().into_owned());
+
+        Self {
+            id: quote(&self.id),
+            version: quote(&self.version),
+            sequence: quote(&self.sequence),
+            revision: quote(&self.revision),
+            stored_on: quote(&self.stored_on),
+            taken_on: quote(&self.taken_on),
+            kind: quote(&self.kind),
+            content: quote(&self.content),
+            correlation_id: quote(&self.correlation_id),
+            message_id: quote(&self.message_id),
+        }
+    }
+}
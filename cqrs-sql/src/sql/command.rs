@@ -1,7 +1,11 @@
-use super::Ident;
+use super::{Columns, Ident};
 use sqlx::{Database, Encode, QueryBuilder, Type};
 
-pub fn delete<'a, ID, DB>(table: &'a Ident<'a>, id: &'a ID) -> QueryBuilder<'a, DB>
+pub fn delete<'a, ID, DB>(
+    table: &'a Ident<'a>,
+    columns: &Columns,
+    id: &'a ID,
+) -> QueryBuilder<'a, DB>
 where
     DB: Database,
     ID: Encode<'a, DB> + Send + Type<DB> + 'a,
@@ -10,7 +14,9 @@ where
 
     delete
         .push(table.quote())
-        .push(" WHERE id = ")
+        .push(" WHERE ")
+        .push(&columns.id)
+        .push(" = ")
         .push_bind(id)
         .push(';');
 
@@ -0,0 +1,59 @@
+use std::borrow::Cow;
+
+/// Defines the possible storage types of message content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContentType {
+    /// Indicates the content is stored as opaque, encoded bytes.
+    #[default]
+    Binary,
+
+    /// Indicates the content is stored as queryable JSON.
+    ///
+    /// # Remarks
+    ///
+    /// The messages must be encoded as UTF-8 JSON; for example, using `encoding::Json`. The
+    /// database may normalize the stored JSON, such as whitespace and the order of keys, which
+    /// does not change the decoded message.
+    Json,
+}
+
+/// Defines the behavior of a database that converts message content to and from its storage type.
+pub trait ContentFormat {
+    /// Gets the expression that reads a content column as encoded bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - the name of the content column
+    /// * `content_type` - the storage [type](ContentType) of the content column
+    fn read(column: &str, content_type: ContentType) -> Cow<'_, str> {
+        let _ = content_type;
+        Cow::Borrowed(column)
+    }
+
+    /// Gets the SQL that encloses a bound parameter to convert encoded bytes into the storage type.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - the storage [type](ContentType) of the content column
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the SQL that precedes and follows the bound parameter.
+    fn write(content_type: ContentType) -> (&'static str, &'static str) {
+        let _ = content_type;
+        ("", "")
+    }
+}
+
+/// Represents an additional condition with a single bound value.
+#[derive(Clone)]
+pub(crate) struct Condition {
+    /// Gets the SQL that precedes the bound value.
+    pub prefix: String,
+
+    /// Gets the bound value.
+    pub value: String,
+
+    /// Gets the SQL that follows the bound value.
+    pub suffix: &'static str,
+}
@@ -0,0 +1,16 @@
+use sqlx::{Database, Type};
+
+/// Defines the behavior of an identifier type that is stored in a SQL database.
+///
+/// # Remarks
+///
+/// Queries bind and read identifiers using their [type](Type), while migrations create the
+/// identifier column using the [database type](SqlIdType::sql_type). Implementations are provided
+/// for the integer, [UUID](uuid::Uuid) and [String] types supported by each database. A new type
+/// identifier, such as a ULID or a longer string, can be stored by implementing this trait along
+/// with [Type], typically using `#[sqlx(transparent)]`. A composite key is stored in a single
+/// column; for example, by encoding its parts as text.
+pub trait SqlIdType<DB: Database>: Type<DB> {
+    /// Gets the database type of the identifier column.
+    fn sql_type() -> &'static str;
+}
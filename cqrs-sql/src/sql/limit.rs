@@ -0,0 +1,5 @@
+/// Defines the behavior of a database that limits the number of bind parameters in a statement.
+pub trait BindLimit {
+    /// Gets the maximum number of bind parameters allowed in a single statement.
+    fn max_binds() -> usize;
+}
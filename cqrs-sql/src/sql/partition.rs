@@ -0,0 +1,58 @@
+/// Defines the possible partitioning schemes of an events table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Partitioning {
+    /// Indicates the table is not partitioned.
+    #[default]
+    None,
+
+    /// Indicates the table is partitioned by a hash of the identifier into the specified number
+    /// of partitions.
+    ///
+    /// # Remarks
+    ///
+    /// All of the events for an identifier are stored in the same partition. The number of
+    /// partitions cannot be changed once the table has been created.
+    Hash(u16),
+
+    /// Indicates the table is partitioned by ranges of the date the events were stored on.
+    ///
+    /// # Remarks
+    ///
+    /// The partitions for the current and next [interval](Interval) are created with the table,
+    /// while future partitions must be created periodically. Events stored outside of every
+    /// interval, such as imported historical events, are stored in a default partition. A
+    /// partition cannot be created for an interval that already has events in the default
+    /// partition. Old partitions can be archived by detaching or dropping them. The primary key and
+    /// message identifier index must include the partition key, so each save locks the events for
+    /// its identifier and verifies the version and message identifier are unique in the same
+    /// transaction instead.
+    Range(Interval),
+}
+
+/// Defines the possible intervals of a range partition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interval {
+    /// Indicates a daily partition.
+    Day,
+
+    /// Indicates a weekly partition, which starts on Monday.
+    Week,
+
+    /// Indicates a monthly partition.
+    Month,
+
+    /// Indicates a yearly partition.
+    Year,
+}
+
+impl Interval {
+    /// Gets the name of the interval, which is also the field used to truncate a date.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+}
@@ -31,6 +31,9 @@ pub struct Row<ID> {
 
     /// Gets or sets the event correlation identifier, if any.
     pub correlation_id: Option<String>,
+
+    /// Gets or sets the identifier of the message that produced the event, if any.
+    pub message_id: Option<String>,
 }
 
 impl<ID: Clone> Row<ID> {
@@ -47,6 +50,7 @@ impl<ID: Clone> Row<ID> {
                 revision: self.revision,
                 content: Default::default(),
                 correlation_id: None,
+                message_id: None,
             })
         }
     }
@@ -58,6 +62,7 @@ pub struct Context<'a, ID, M: ?Sized + Sync> {
     pub version: Version,
     pub clock: &'a dyn Clock,
     pub transcoder: &'a Transcoder<M>,
+    pub message_id: Option<&'a str>,
 }
 
 /// Defines the behavior to iterate events as rows.
@@ -127,6 +132,11 @@ where
                 revision: schema.version() as i16,
                 content,
                 correlation_id: event.correlation_id().map(Into::into),
+                message_id: if i == 0 {
+                    self.context.message_id.map(Into::into)
+                } else {
+                    None
+                },
             }))
         } else {
             None
@@ -1,16 +1,57 @@
 mod common;
 
 use common::{
-    domain::{self, Account},
+    domain::{self, Account, Opened},
     scenario, TestResult,
 };
-use cqrs::{snapshot::Store, Repository, RepositoryError};
+use cqrs::{
+    event::{self, ContinuationToken, Direction, Event, ExpectedVersion, PredicateBuilder},
+    projection::Sink,
+    snapshot::Store,
+    Aggregate, Repository, RepositoryError, UnitOfWork, Version,
+};
 use cqrs_sql::{
-    sqlite::{EventStore, Migrator, SnapshotStore},
+    projection::ReadModel,
+    sql::{Columns, SqlIdType},
+    sqlite::{EventStore, Migrator, ProjectionSink, SnapshotStore},
     SqlStoreMigration,
 };
-use sqlx::sqlite::SqlitePoolOptions;
-use std::sync::Arc;
+use futures::TryStreamExt;
+use sqlx::{Sqlite, migrate::Migration, query_builder::Separated, sqlite::SqlitePoolOptions};
+use std::{
+    error::Error,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(transparent)]
+struct Sku(String);
+
+impl SqlIdType<Sqlite> for Sku {
+    fn sql_type() -> &'static str {
+        "VARCHAR(200)"
+    }
+}
+
+struct Balance {
+    checkpoint: i64,
+    amount: f64,
+}
+
+impl ReadModel<Sqlite> for Balance {
+    fn columns() -> &'static [&'static str] {
+        &["amount"]
+    }
+
+    fn checkpoint(&self) -> i64 {
+        self.checkpoint
+    }
+
+    fn bind<'a>(&'a self, values: &mut Separated<'_, 'a, Sqlite, &'static str>) {
+        values.push_bind(self.amount);
+    }
+}
 
 #[tokio::test]
 async fn verify_sqlite_integration() -> TestResult {
@@ -95,3 +136,805 @@ async fn verify_sqlite_does_not_allow_save_after_delete() -> TestResult {
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn verify_sqlite_loads_raw_events() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_0c5d3e0f1b8a4c6e9a7d2f4b6e8c1a3d")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let id = scenario::open_new_account(&repository, "12345", 50.0).await?;
+
+    scenario::make_deposit(&repository, &id, 200.0).await?;
+
+    let predicate = PredicateBuilder::new(Some(&id)).build();
+
+    // act
+    let raw: Vec<_> = event::Store::load_raw(&*events, Some(&predicate))
+        .await
+        .try_collect()
+        .await?;
+
+    // assert
+    let amounts: Vec<_> = raw
+        .iter()
+        .map(|event| event.descriptor().to_json().unwrap()["amount"].clone())
+        .collect();
+
+    assert!(raw.iter().all(|event| event.id() == &id));
+    assert_eq!(amounts, vec![50.0, 200.0]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_saves_events_once_per_message() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_7e1b9c2d4f6a48e0b3c5d7e9f1a2b4c6")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+    let mut retry = Account::open("12345", 50.0);
+
+    account.credit(200.0)?;
+    retry.credit(200.0)?;
+    repository.save_once(&mut account, "deposit-1").await?;
+
+    // act
+    repository.save_once(&mut retry, "deposit-1").await?;
+
+    // assert
+    let predicate = PredicateBuilder::new(Some(account.id())).build();
+    let raw: Vec<_> = event::Store::load_raw(&*events, Some(&predicate))
+        .await
+        .try_collect()
+        .await?;
+
+    assert_eq!(raw.len(), 2);
+    assert_eq!(account.version(), retry.version());
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_saves_events_with_expected_version() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_5d8f1a3c7e9b42d6a0c4e8f2b6d1a9e3")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+    let mut duplicate = Account::open("12345", 75.0);
+    let mut missing = Account::open("67890", 25.0);
+
+    repository
+        .save_expecting(&mut account, ExpectedVersion::NoStream)
+        .await?;
+
+    // act
+    let conflict = repository
+        .save_expecting(&mut duplicate, ExpectedVersion::NoStream)
+        .await;
+    let not_found = repository
+        .save_expecting(&mut missing, ExpectedVersion::StreamExists)
+        .await;
+
+    account.credit(200.0)?;
+    repository
+        .save_expecting(&mut account, ExpectedVersion::StreamExists)
+        .await?;
+
+    // assert
+    assert!(matches!(conflict, Err(RepositoryError::Conflict(_, _))));
+    assert!(matches!(not_found, Err(RepositoryError::NotFound(_))));
+    assert_eq!(repository.get(account.id(), None).await?.balance(), 250.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_saves_exact_version_after_multiple_events() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_6a8c0e2b4d1f4a3c5e7b9d1f3a5c7e9b")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+
+    account.credit(25.0)?;
+    repository.save(&mut account).await?;
+
+    let version = account.version();
+
+    account.credit(10.0)?;
+
+    // act
+    repository
+        .save_expecting(&mut account, ExpectedVersion::Exact(version))
+        .await?;
+
+    // assert
+    let head = event::Store::head(&*events, &"12345".into()).await?;
+
+    assert_ne!(account.version(), version);
+    assert_eq!(head.version(), account.version());
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_rejects_exact_version_not_at_stream() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_2c7e9a4f1b3d4e8a9c6f0b2d5e7a1c3f")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+    let mut other = Account::open("67890", 50.0);
+    let mut ahead = Account::open("12345", 75.0);
+    let mut missing = Account::open("24680", 25.0);
+
+    repository.save(&mut account).await?;
+    repository.save(&mut other).await?;
+    other.credit(25.0)?;
+    repository.save(&mut other).await?;
+
+    // act
+    let conflict = repository
+        .save_expecting(&mut ahead, ExpectedVersion::Exact(other.version()))
+        .await;
+    let not_found = repository
+        .save_expecting(&mut missing, ExpectedVersion::Exact(account.version()))
+        .await;
+
+    // assert
+    let head = event::Store::head(&*events, &"12345".into()).await?;
+
+    assert!(matches!(conflict, Err(RepositoryError::Conflict(_, _))));
+    assert!(matches!(not_found, Err(RepositoryError::NotFound(_))));
+    assert_eq!(head.version(), account.version());
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_reads_stream_head() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_9c3e5a7b1d2f4086b8e0a2c4d6f8b0e2")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+
+    repository.save(&mut account).await?;
+    account.credit(200.0)?;
+    account.debit(25.0)?;
+    repository.save(&mut account).await?;
+
+    // act
+    let head = event::Store::head(&*events, &"12345".into()).await?;
+    let missing = event::Store::head(&*events, &"67890".into()).await?;
+
+    // assert
+    assert!(head.exists());
+    assert_eq!(head.version(), account.version());
+    assert_eq!(head.count(), 3);
+    assert!(head.first_stored_on().is_some());
+    assert!(!missing.exists());
+    assert_eq!(missing.last_stored_on(), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_reads_pages_of_events() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_0f2d4b6a8c1e43579b1d3f5a7c9e0b2d")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+    let mut other = Account::open("67890", 10.0);
+
+    account.credit(200.0)?;
+    repository.save(&mut account).await?;
+    account.debit(25.0)?;
+    repository.save(&mut account).await?;
+    repository.save(&mut other).await?;
+
+    let id = account.id().clone();
+    let last = PredicateBuilder::new(Some(&id))
+        .direction(Direction::Backward)
+        .limit(2)
+        .build();
+    let first = PredicateBuilder::new(None).limit(2).build();
+
+    // act
+    let newest: Vec<_> = event::Store::load(&*events, Some(&last))
+        .await
+        .try_collect()
+        .await?;
+    let page: Vec<_> = event::Store::load_raw(&*events, Some(&first))
+        .await
+        .try_collect()
+        .await?;
+    let token = ContinuationToken::from(page.last().unwrap());
+    let rest = PredicateBuilder::new(None).limit(2).after(&token).build();
+    let next: Vec<_> = event::Store::load_raw(&*events, Some(&rest))
+        .await
+        .try_collect()
+        .await?;
+
+    // assert
+    assert_eq!(newest.len(), 2);
+    assert_eq!(newest[0].version(), account.version());
+    assert!(newest[0].version() > newest[1].version());
+    assert_eq!(page.len(), 2);
+    assert_eq!(next.len(), 2);
+    assert_eq!(next[0].id(), &id);
+    assert_eq!(next[1].id(), other.id());
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_reads_pages_of_ids() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_6b8d0f2a4c6e48a1b3d5f7092c4e6a8b")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+
+    for id in ["3", "1", "2"] {
+        repository.save(&mut Account::open(id, 10.0)).await?;
+    }
+
+    let first = event::Store::ids_page(&*events, Default::default(), 2, None).await?;
+
+    // act
+    let next = event::Store::ids_page(&*events, Default::default(), 2, first.token()).await?;
+
+    // assert
+    assert_eq!(first.ids(), ["1", "2"]);
+    assert_eq!(first.token(), Some(&"2".to_owned()));
+    assert_eq!(next.ids(), ["3"]);
+    assert_eq!(next.token(), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_saves_unit_of_work_atomically() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_2a4c6e8f0b1d43f5a7c9e1b3d5f7a9c1")
+            .transcoder(domain::transcoder::events())
+            .enforce_concurrency()
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let store = events.clone() as Arc<dyn event::Store<String>>;
+    let repository: Repository<Account> = store.clone().into();
+    let mut savings = Account::open("1", 100.0);
+    let mut checking = Account::open("2", 0.0);
+    let mut work: UnitOfWork<String> = store.clone().into();
+
+    work.register(&mut savings);
+    work.register(&mut checking);
+    work.commit().await?;
+
+    let mut stale = repository.get(&"2".to_owned(), None).await?;
+
+    savings.debit(25.0)?;
+    checking.credit(25.0)?;
+    stale.credit(5.0)?;
+    repository.save(&mut stale).await?;
+
+    let mut work: UnitOfWork<String> = store.into();
+
+    work.register(&mut savings);
+    work.register(&mut checking);
+
+    // act
+    let result = work.commit().await;
+
+    // assert
+    let savings = repository.get(&"1".to_owned(), None).await?;
+    let checking = repository.get(&"2".to_owned(), None).await?;
+
+    assert!(matches!(result, Err(RepositoryError::Conflict(_, _))));
+    assert_eq!(savings.balance(), 100.0);
+    assert_eq!(checking.balance(), 5.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_saves_events_in_external_transaction() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_9d8c7b6a5f4e43d2b1a0c9e8d7f6a5b4")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite.clone()));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let id = "12345".to_owned();
+    let predicate = PredicateBuilder::new(Some(&id)).build();
+    let mut account = Account::open(&id, 50.0);
+    let mut tx = sqlite.begin().await?;
+    let mut changes = account.changes();
+
+    events
+        .save_in(&mut tx, &id, changes.expected_version(), changes.uncommitted())
+        .await?;
+
+    let uncommitted: Vec<_> = events
+        .load_in(&mut tx, Some(&predicate))
+        .await
+        .try_collect()
+        .await?;
+
+    tx.rollback().await?;
+
+    let rolled_back = repository.get(&id, None).await;
+    let mut tx = sqlite.begin().await?;
+
+    // act
+    let version = events
+        .save_in(&mut tx, &id, changes.expected_version(), changes.uncommitted())
+        .await?;
+
+    tx.commit().await?;
+    changes.accept(version);
+
+    // assert
+    let account = repository.get(&id, None).await?;
+
+    assert_eq!(uncommitted.len(), 1);
+    assert_eq!(rolled_back.err().unwrap(), RepositoryError::NotFound(id.clone()));
+    assert_eq!(account.balance(), 50.0);
+    assert_eq!(account.version(), version);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_writes_read_models_idempotently() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let sink = ProjectionSink::new("TMP_0c7e2b9d4a1f4e6b8d3a5c7e9f1b3d5a", sqlite.clone());
+    let id = String::from("42");
+
+    sqlx::query(concat!(
+        "CREATE TABLE TMP_0c7e2b9d4a1f4e6b8d3a5c7e9f1b3d5a ",
+        "(id TEXT PRIMARY KEY, checkpoint INTEGER NOT NULL, amount REAL NOT NULL);"
+    ))
+    .execute(&sqlite)
+    .await?;
+
+    let first = Balance {
+        checkpoint: 2,
+        amount: 100.0,
+    };
+    let earlier = Balance {
+        checkpoint: 1,
+        amount: 50.0,
+    };
+    let later = Balance {
+        checkpoint: 3,
+        amount: 75.0,
+    };
+
+    sink.write(&id, first)
+        .await
+        .map_err(|e| e as Box<dyn Error>)?;
+
+    // act
+    sink.write(&id, earlier)
+        .await
+        .map_err(|e| e as Box<dyn Error>)?;
+    let stale: f64 = sqlx::query_scalar("SELECT amount FROM TMP_0c7e2b9d4a1f4e6b8d3a5c7e9f1b3d5a;")
+        .fetch_one(&sqlite)
+        .await?;
+    sink.write(&id, later)
+        .await
+        .map_err(|e| e as Box<dyn Error>)?;
+    let fresh: f64 = sqlx::query_scalar("SELECT amount FROM TMP_0c7e2b9d4a1f4e6b8d3a5c7e9f1b3d5a;")
+        .fetch_one(&sqlite)
+        .await?;
+    let checkpoint = sink
+        .checkpoint(&id)
+        .await
+        .map_err(|e| e as Box<dyn Error>)?;
+
+    // assert
+    assert_eq!(stale, 100.0);
+    assert_eq!(fresh, 75.0);
+    assert_eq!(checkpoint, Some(3));
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_saves_batch_of_events() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_8e2c4a6f0b1d43e5a7c9f1b3d5e7a9c1")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+
+    for _ in 0..200 {
+        account.credit(1.0)?;
+    }
+
+    // act
+    repository.save(&mut account).await?;
+
+    // assert
+    let account = repository.get(&"12345".to_owned(), None).await?;
+    let predicate = PredicateBuilder::new(Some(account.id())).build();
+    let raw: Vec<_> = event::Store::load_raw(&*events, Some(&predicate))
+        .await
+        .try_collect()
+        .await?;
+
+    assert_eq!(raw.len(), 201);
+    assert_eq!(account.balance(), 250.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_migrates_schema_incrementally() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_3f7a9c1e5b2d4086a4c6e8f0b2d4f6a8")
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrations: Vec<Migration> = (&*events).into();
+    let initial = Migrator::new();
+
+    initial.add(SqlStoreMigration::with_pool(
+        migrations[..1].to_vec(),
+        sqlite.clone(),
+    ));
+    initial.run().await?;
+
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+
+    // act
+    migrator.run().await?;
+
+    // assert
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let mut account = Account::open("12345", 50.0);
+    let mut retry = Account::open("12345", 50.0);
+
+    repository.save_once(&mut account, "open-1").await?;
+    repository.save_once(&mut retry, "open-1").await?;
+
+    assert!(migrations.len() > 1);
+    assert_eq!(account.version(), retry.version());
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_migrates_store_added_to_migrated_database() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: EventStore<String> = EventStore::builder()
+        .pool(sqlite.clone())
+        .table("TMP_8c1d5e7f9a2b4c6d8e0f1a3b5c7d9e2f")
+        .transcoder(domain::transcoder::events())
+        .try_into()?;
+    let snapshots: Arc<SnapshotStore<String>> = Arc::new(
+        SnapshotStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_5b7d9f1a3c5e4a7c9e1b3d5f7a9c2e4b")
+            .transcoder(domain::transcoder::snapshots())
+            .try_into()?,
+    );
+    let initial = Migrator::new();
+
+    initial.add(SqlStoreMigration::with_pool(&events, sqlite.clone()));
+    initial.run().await?;
+
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&events, sqlite.clone()));
+    migrator.add(SqlStoreMigration::with_pool(&*snapshots, sqlite.clone()));
+
+    // act
+    migrator.run().await?;
+
+    // assert
+    let only_snapshots = Migrator::new();
+
+    only_snapshots.add(SqlStoreMigration::with_pool(&*snapshots, sqlite));
+    only_snapshots.run().await?;
+
+    let repository = Repository::<Account>::new(events);
+    let id = scenario::open_new_account(&repository, "12345", 50.0).await?;
+
+    scenario::new_monthly_statement(&repository, &id, &*snapshots).await?;
+    assert!(snapshots.load(&id, None).await?.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_creates_secondary_indexes() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: EventStore<String> = EventStore::builder()
+        .pool(sqlite.clone())
+        .table("TMP_5b9d1f3a7c2e4860b2d4f6a8c0e2a4b6")
+        .transcoder(domain::transcoder::events())
+        .try_into()?;
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&events, sqlite.clone()));
+
+    // act
+    migrator.run().await?;
+
+    // assert
+    let indexes: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master \
+         WHERE type = 'index' AND tbl_name LIKE '%TMP_5b9d1f3a7c2e4860b2d4f6a8c0e2a4b6'",
+    )
+    .fetch_all(&sqlite)
+    .await?;
+    let indexes: Vec<_> = indexes.into_iter().map(|(name,)| name).collect();
+
+    assert!(indexes.iter().any(|name| name.ends_with("_stored_on")));
+    assert!(indexes.iter().any(|name| name.ends_with("_type_revision")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_uses_legacy_table_with_mapped_columns() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+
+    sqlx::query(
+        "CREATE TABLE legacy_events(\
+         stream_id TEXT NOT NULL, \
+         stream_version INTEGER NOT NULL, \
+         position INTEGER NOT NULL, \
+         \"schema version\" INTEGER NOT NULL, \
+         created INTEGER NOT NULL, \
+         \"event type\" TEXT NOT NULL, \
+         payload BLOB NOT NULL, \
+         correlation TEXT DEFAULT NULL, \
+         PRIMARY KEY(stream_id, stream_version, position));",
+    )
+    .execute(&sqlite)
+    .await?;
+
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite)
+            .schema("")
+            .table("legacy_events")
+            .columns(Columns {
+                id: "stream_id".into(),
+                version: "stream_version".into(),
+                sequence: "position".into(),
+                revision: "schema version".into(),
+                stored_on: "created".into(),
+                kind: "event type".into(),
+                content: "payload".into(),
+                correlation_id: "correlation".into(),
+                ..Default::default()
+            })
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+    let id = scenario::open_new_account(&repository, "12345", 50.0).await?;
+
+    // act
+    let balance = scenario::make_deposit(&repository, &id, 25.0).await?;
+
+    // assert
+    let head = event::Store::head(&*events, &id).await?;
+    let ids: Vec<_> = event::Store::ids(&*events, Default::default())
+        .await
+        .try_collect()
+        .await?;
+
+    assert_eq!(balance, 75.0);
+    assert_eq!(head.count(), 2);
+    assert_eq!(ids, vec![id]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_stores_events_with_custom_id_type() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    let events: EventStore<Sku> = EventStore::builder()
+        .pool(sqlite.clone())
+        .table("TMP_2e4a6c8e0b2d4f6a8c0e2b4d6f8a0c2e")
+        .transcoder(domain::transcoder::events())
+        .try_into()?;
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&events, sqlite.clone()));
+    migrator.run().await?;
+
+    let id = Sku("SKU-12345".into());
+    let opened: Box<dyn Event> = Box::new(Opened::new("12345".into(), 50.0, SystemTime::now()));
+
+    // act
+    event::Store::save(&events, &id, Version::default(), &[opened]).await?;
+
+    // assert
+    let (kind,): (String,) = sqlx::query_as(
+        "SELECT type FROM pragma_table_info('events_TMP_2e4a6c8e0b2d4f6a8c0e2b4d6f8a0c2e') \
+         WHERE name = 'id'",
+    )
+    .fetch_one(&sqlite)
+    .await?;
+    let ids: Vec<_> = event::Store::ids(&events, Default::default())
+        .await
+        .try_collect()
+        .await?;
+
+    assert_eq!(kind, "VARCHAR(200)");
+    assert_eq!(ids, vec![id]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_sqlite_releases_connection_between_pages() -> TestResult {
+    // arrange
+    let sqlite = SqlitePoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(1))
+        .connect("sqlite::memory:")
+        .await?;
+    let events: Arc<EventStore<String>> = Arc::new(
+        EventStore::builder()
+            .pool(sqlite.clone())
+            .table("TMP_5a7c9e1b3d5f47a9b2c4e6a8d0f1b3c5")
+            .page_size(2)
+            .transcoder(domain::transcoder::events())
+            .try_into()?,
+    );
+    let migrator = Migrator::new();
+
+    migrator.add(SqlStoreMigration::with_pool(&*events, sqlite));
+    migrator.run().await?;
+
+    let repository: Repository<Account> = (events.clone() as Arc<dyn event::Store<String>>).into();
+
+    scenario::open_new_account(&repository, "1", 50.0).await?;
+    scenario::open_new_account(&repository, "2", 10.0).await?;
+    scenario::open_new_account(&repository, "3", 75.0).await?;
+    scenario::make_deposit(&repository, &"1".to_owned(), 25.0).await?;
+
+    let mut stream = event::Store::load_raw(&*events, None).await;
+
+    // act
+    let first = stream.try_next().await?.unwrap();
+    let head = event::Store::head(&*events, &"2".to_owned()).await?;
+    let rest: Vec<_> = stream.try_collect().await?;
+    let ids: Vec<_> = event::Store::ids(&*events, Default::default())
+        .await
+        .try_collect()
+        .await?;
+
+    // assert
+    let read: Vec<_> = std::iter::once(&first)
+        .chain(&rest)
+        .map(|event| event.id().as_str())
+        .collect();
+
+    assert_eq!(head.count(), 1);
+    assert_eq!(read, vec!["1", "1", "2", "3"]);
+    assert_eq!(ids, vec!["1", "2", "3"]);
+    Ok(())
+}
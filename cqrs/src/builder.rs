@@ -1,6 +1,6 @@
 use crate::{
     Clock, Concurrency, Mask, WallClock,
-    event::{self, Delete, Event},
+    event::{self, DecodePolicy, Delete, Event},
     message::{Message, Transcoder},
     snapshot::{self, Snapshot},
 };
@@ -10,6 +10,7 @@ use std::sync::Arc;
 pub struct StoreOptionsBuilder<M: Message + ?Sized, ID = ()> {
     concurrency: Concurrency,
     delete: Delete,
+    decode: DecodePolicy,
     mask: Option<Arc<dyn Mask>>,
     clock: Option<Arc<dyn Clock>>,
     transcoder: Option<Arc<Transcoder<M>>>,
@@ -21,6 +22,7 @@ impl<M: Message + ?Sized, ID> Default for StoreOptionsBuilder<M, ID> {
         Self {
             concurrency: Default::default(),
             delete: Default::default(),
+            decode: Default::default(),
             mask: Default::default(),
             clock: Default::default(),
             transcoder: Default::default(),
@@ -74,6 +76,16 @@ impl<ID> StoreOptionsBuilder<dyn Event, ID> {
         self
     }
 
+    /// Configures the behavior when a stored event cannot be decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the [decode policy](DecodePolicy) to apply
+    pub fn decode_policy(mut self, value: DecodePolicy) -> Self {
+        self.decode = value;
+        self
+    }
+
     /// Configures the snapshots associated with the store.
     ///
     /// # Arguments
@@ -86,14 +98,17 @@ impl<ID> StoreOptionsBuilder<dyn Event, ID> {
 
     /// Builds and returns a new [event store options](event::StoreOptions).
     pub fn build(self) -> event::StoreOptions<ID> {
-        event::StoreOptions::<ID>::new(
+        let mut options = event::StoreOptions::<ID>::new(
             self.concurrency,
             self.delete,
             self.mask,
             self.clock.unwrap_or_else(|| Arc::new(WallClock::new())),
             self.transcoder.unwrap_or_default(),
             self.snapshots,
-        )
+        );
+
+        options.set_decode_policy(self.decode);
+        options
     }
 }
 
@@ -1,5 +1,19 @@
 use cfg_if::cfg_if;
 
+cfg_if! {
+    if #[cfg(feature = "avro")] {
+        mod avro;
+        pub use avro::Avro;
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "bincode")] {
+        mod bincode;
+        pub use self::bincode::Bincode;
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "cbor")] {
         mod cbor;
@@ -7,6 +21,14 @@ cfg_if! {
     }
 }
 
+cfg_if! {
+    if #[cfg(any(feature = "gzip", feature = "lz4", feature = "zstd"))] {
+        mod compressed;
+        pub use compressed::{Compressed, Compression};
+        pub(crate) use compressed::decompress;
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "json")] {
         mod json;
@@ -21,6 +43,13 @@ cfg_if! {
     }
 }
 
+cfg_if! {
+    if #[cfg(feature = "postcard")] {
+        mod postcard;
+        pub use self::postcard::Postcard;
+    }
+}
+
 cfg_if! {
     if #[cfg(feature = "protobuf")] {
         mod protobuf;
@@ -0,0 +1,261 @@
+use crate::event::Event;
+use crate::message::{Encoded, Encoding, Schema};
+use crate::snapshot::Snapshot;
+use apache_avro::{AvroSchema, from_avro_datum, from_value, to_avro_datum, to_value};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::marker::PhantomData;
+
+/// Represents a message encoding using [Apache Avro](https://avro.apache.org).
+///
+/// # Remarks
+///
+/// Each message [schema](Schema) revision maps to the Avro schema its content was written with. Content is always
+/// decoded by resolving the writer schema against the Avro schema of the current message type, which allows
+/// previous revisions of a message to be decoded into the current message type using
+/// [Avro schema resolution](https://avro.apache.org/docs/current/specification/#schema-resolution).
+pub struct Avro<T> {
+    schema: Schema,
+    writer: apache_avro::Schema,
+    reader: apache_avro::Schema,
+    _marker: PhantomData<T>,
+}
+
+impl<T: AvroSchema> Avro<T> {
+    /// Initializes a new [Avro](Avro) message encoding for the specified message type and version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the supported message version
+    pub fn version(version: u8) -> Self {
+        Self {
+            schema: Schema::new(std::any::type_name::<T>(), version),
+            writer: T::get_schema(),
+            reader: T::get_schema(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T: Encoded + AvroSchema> Default for Avro<T> {
+    fn default() -> Self {
+        Self {
+            schema: T::schema(),
+            writer: T::get_schema(),
+            reader: T::get_schema(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T: Encoded + AvroSchema> Avro<T> {
+    /// Initializes a new [Avro](Avro) message encoding for the specified [encoded](Encoded) message type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Initializes a new [Avro](Avro) message encoding for a previous revision of the specified
+    /// [encoded](Encoded) message type.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the previous message version
+    /// * `writer` - the Avro schema the previous message version was written with
+    ///
+    /// # Remarks
+    ///
+    /// Content written with the previous revision is decoded into the current message type. The writer schema
+    /// must be compatible with the Avro schema of the current message type.
+    pub fn revision(version: u8, writer: apache_avro::Schema) -> Self {
+        Self {
+            schema: Schema::new(T::schema().kind(), version),
+            writer,
+            reader: T::get_schema(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T> Avro<T> {
+    /// Gets the Avro schema message content is written with.
+    pub fn writer(&self) -> &apache_avro::Schema {
+        &self.writer
+    }
+
+    /// Gets the Avro schema message content is read with.
+    pub fn reader(&self) -> &apache_avro::Schema {
+        &self.reader
+    }
+
+    fn encode_as(&self, message: &T) -> Result<Vec<u8>, Box<dyn Error + Send>>
+    where
+        T: Serialize,
+    {
+        to_value(message)
+            .and_then(|value| to_avro_datum(&self.writer, value))
+            .map_err(|err| Box::new(err) as Box<dyn Error + Send>)
+    }
+
+    fn decode_as(&self, mut message: &[u8]) -> Result<T, Box<dyn Error + Send>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        from_avro_datum(&self.writer, &mut message, Some(&self.reader))
+            .and_then(|value| from_value::<T>(&value))
+            .map_err(|err| Box::new(err) as Box<dyn Error + Send>)
+    }
+}
+
+impl<T> Encoding<dyn Event> for Avro<T>
+where
+    T: Default + for<'de> Deserialize<'de> + Serialize + Event + 'static,
+{
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn encode(&self, message: &dyn Event) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        self.encode_as(message.as_any().downcast_ref::<T>().unwrap())
+    }
+
+    fn decode(&self, message: &[u8]) -> Result<Box<dyn Event>, Box<dyn Error + Send>> {
+        Ok(self
+            .decode_as(message)
+            .map(|event| Box::new(event) as Box<dyn Event + Send>)?)
+    }
+}
+
+impl<T> Encoding<dyn Snapshot> for Avro<T>
+where
+    T: Default + for<'de> Deserialize<'de> + Serialize + Snapshot + 'static,
+{
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn encode(&self, message: &dyn Snapshot) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        self.encode_as(message.as_any().downcast_ref::<T>().unwrap())
+    }
+
+    fn decode(&self, message: &[u8]) -> Result<Box<dyn Snapshot>, Box<dyn Error + Send>> {
+        Ok(self
+            .decode_as(message)
+            .map(|snapshot| Box::new(snapshot) as Box<dyn Snapshot + Send>)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Message;
+    use apache_avro::types::Value;
+    use std::any::Any;
+
+    const PLACED_V1: &str = r#"
+        {
+            "type": "record",
+            "name": "Placed",
+            "fields": [
+                { "name": "id", "type": "string" }
+            ]
+        }"#;
+
+    const PLACED_V2: &str = r#"
+        {
+            "type": "record",
+            "name": "Placed",
+            "fields": [
+                { "name": "id", "type": "string" },
+                { "name": "total", "type": "double", "default": 0.0 }
+            ]
+        }"#;
+
+    #[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+    struct Placed {
+        id: String,
+        total: f64,
+    }
+
+    impl AvroSchema for Placed {
+        fn get_schema() -> apache_avro::Schema {
+            apache_avro::Schema::parse_str(PLACED_V2).unwrap()
+        }
+    }
+
+    impl Encoded for Placed {
+        fn schema() -> Schema {
+            Schema::new("urn:test:placed", 2)
+        }
+    }
+
+    impl Message for Placed {
+        fn schema(&self) -> Schema {
+            <Self as Encoded>::schema()
+        }
+    }
+
+    impl Event for Placed {
+        fn name(&self) -> &str {
+            "Placed"
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn event_should_roundtrip_with_avro() {
+        // arrange
+        let encoding = Avro::<Placed>::new();
+        let expected = Placed {
+            id: "42".into(),
+            total: 100.0,
+        };
+
+        // act
+        let binary = Encoding::<dyn Event>::encode(&encoding, &expected).unwrap();
+        let event = Encoding::<dyn Event>::decode(&encoding, &binary).unwrap();
+        let actual = event.as_any().downcast_ref::<Placed>().unwrap();
+
+        // assert
+        assert_eq!(actual, &expected);
+    }
+
+    #[test]
+    fn previous_revision_should_decode_into_current_event() {
+        // arrange
+        let writer = apache_avro::Schema::parse_str(PLACED_V1).unwrap();
+        let record = Value::Record(vec![("id".into(), Value::String("42".into()))]);
+        let binary = to_avro_datum(&writer, record).unwrap();
+        let encoding = Avro::<Placed>::revision(1, writer);
+
+        // act
+        let event = Encoding::<dyn Event>::decode(&encoding, &binary).unwrap();
+        let actual = event.as_any().downcast_ref::<Placed>().unwrap();
+
+        // assert
+        assert_eq!(Encoding::<dyn Event>::schema(&encoding).version(), 1);
+        assert_eq!(
+            actual,
+            &Placed {
+                id: "42".into(),
+                total: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_content_should_not_decode() {
+        // arrange
+        let encoding = Avro::<Placed>::new();
+        let binary = [0xff_u8; 3];
+
+        // act
+        let result = Encoding::<dyn Event>::decode(&encoding, &binary);
+
+        // assert
+        let error: Box<dyn Error + Send> = result.err().unwrap();
+        assert!(!error.to_string().is_empty());
+    }
+}
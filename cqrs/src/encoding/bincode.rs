@@ -0,0 +1,122 @@
+use crate::event::Event;
+use crate::message::{Encoded, Encoding, Schema};
+use crate::snapshot::Snapshot;
+use bincode::config::standard;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::marker::PhantomData;
+
+/// Represents a message encoding using [bincode](https://crates.io/crates/bincode).
+///
+/// # Remarks
+///
+/// Messages are encoded using the standard configuration, which uses variable-length integer encoding.
+pub struct Bincode<T> {
+    schema: Schema,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Bincode<T> {
+    /// Initializes a new [bincode](Bincode) message encoding for the specified message type and version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the supported message version
+    pub fn version(version: u8) -> Self {
+        Self {
+            schema: Schema::new(std::any::type_name::<T>(), version),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T: Encoded> Default for Bincode<T> {
+    fn default() -> Self {
+        Self {
+            schema: T::schema(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T: Encoded> Bincode<T> {
+    /// Initializes a new [bincode](Bincode) message encoding for the specified [encoded](Encoded) message type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T> Encoding<dyn Event> for Bincode<T>
+where
+    T: Default + for<'de> Deserialize<'de> + Serialize + Event + 'static,
+{
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn encode(&self, message: &dyn Event) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        bincode::serde::encode_to_vec(message.as_any().downcast_ref::<T>().unwrap(), standard())
+            .map_err(|err| Box::new(err) as Box<dyn Error + Send>)
+    }
+
+    fn decode(&self, message: &[u8]) -> Result<Box<dyn Event>, Box<dyn Error + Send>> {
+        Ok(
+            bincode::serde::decode_from_slice::<T, _>(message, standard())
+                .map(|(event, _)| Box::new(event) as Box<dyn Event + Send>)
+                .map_err(|err| Box::new(err) as Box<dyn Error + Send>)?,
+        )
+    }
+}
+
+impl<T> Encoding<dyn Snapshot> for Bincode<T>
+where
+    T: Default + for<'de> Deserialize<'de> + Serialize + Snapshot + 'static,
+{
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn encode(&self, message: &dyn Snapshot) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        bincode::serde::encode_to_vec(message.as_any().downcast_ref::<T>().unwrap(), standard())
+            .map_err(|err| Box::new(err) as Box<dyn Error + Send>)
+    }
+
+    fn decode(&self, message: &[u8]) -> Result<Box<dyn Snapshot>, Box<dyn Error + Send>> {
+        Ok(
+            bincode::serde::decode_from_slice::<T, _>(message, standard())
+                .map(|(snapshot, _)| Box::new(snapshot) as Box<dyn Snapshot + Send>)
+                .map_err(|err| Box::new(err) as Box<dyn Error + Send>)?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Version;
+    use bincode::config::standard;
+    use serde::{Deserialize, Serialize};
+    use uuid::Uuid;
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Example {
+        id: Uuid,
+        version: Version,
+    }
+
+    #[test]
+    fn fields_should_roundtrip_with_bincode() {
+        // arrange
+        let expected = Example {
+            id: uuid::Uuid::new_v4().into(),
+            version: Version::new(42),
+        };
+
+        // act
+        let binary = bincode::serde::encode_to_vec(&expected, standard()).unwrap();
+        let (actual, _) =
+            bincode::serde::decode_from_slice::<Example, _>(binary.as_slice(), standard()).unwrap();
+
+        // assert
+        assert_eq!(actual, expected);
+    }
+}
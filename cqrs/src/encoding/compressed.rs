@@ -0,0 +1,318 @@
+use crate::message::{Encoding, Schema};
+use std::borrow::Cow;
+use std::error::Error;
+use std::io::{self, ErrorKind};
+
+// content layout
+//
+// | 8-bits | n-bits            |
+// | ------ | ----------------- |
+// | header | compressed frame  |
+//
+// the header identifies the compression algorithm. the compressed frame always begins with the magic number defined
+// by the algorithm's frame format. both must match for content to be considered compressed. content that does not
+// begin with a known header and magic number is considered uncompressed, which allows compressed and uncompressed
+// content to coexist; for example, when compression is introduced to an existing store.
+
+const GZIP: u8 = 0x01;
+const LZ4: u8 = 0x02;
+const ZSTD: u8 = 0x03;
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+const LZ4_MAGIC: [u8; 4] = [0x04, 0x22, 0x4D, 0x18];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Represents the supported compression algorithms.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// Indicates [gzip](https://www.rfc-editor.org/rfc/rfc1952) compression.
+    #[cfg(feature = "gzip")]
+    Gzip,
+
+    /// Indicates [LZ4](https://github.com/lz4/lz4/blob/dev/doc/lz4_Frame_format.md) compression.
+    #[cfg(feature = "lz4")]
+    Lz4,
+
+    /// Indicates [Zstandard](https://www.rfc-editor.org/rfc/rfc8878) compression.
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    fn header(&self) -> u8 {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => GZIP,
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => LZ4,
+            #[cfg(feature = "zstd")]
+            Self::Zstd => ZSTD,
+        }
+    }
+
+    fn compress(&self, content: &[u8]) -> io::Result<Vec<u8>> {
+        let mut compressed = vec![self.header()];
+
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => {
+                use flate2::{Compression as Level, write::GzEncoder};
+                use std::io::Write;
+
+                let mut encoder = GzEncoder::new(compressed, Level::default());
+                encoder.write_all(content)?;
+                compressed = encoder.finish()?;
+            }
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => {
+                use lz4_flex::frame::FrameEncoder;
+                use std::io::Write;
+
+                let mut encoder = FrameEncoder::new(compressed);
+                encoder.write_all(content)?;
+                compressed = encoder.finish().map_err(io::Error::other)?;
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd => {
+                zstd::stream::copy_encode(
+                    content,
+                    &mut compressed,
+                    zstd::DEFAULT_COMPRESSION_LEVEL,
+                )?;
+            }
+        }
+
+        Ok(compressed)
+    }
+}
+
+fn unsupported(header: u8) -> io::Error {
+    let algorithm = match header {
+        GZIP => "gzip",
+        LZ4 => "lz4",
+        _ => "zstd",
+    };
+
+    io::Error::new(
+        ErrorKind::Unsupported,
+        format!(
+            "the content is compressed with {algorithm}, but the '{algorithm}' feature is not enabled"
+        ),
+    )
+}
+
+pub(crate) fn decompress(content: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+    let Some((&header, frame)) = content.split_first() else {
+        return Ok(Cow::Borrowed(content));
+    };
+    let magic: &[u8] = match header {
+        GZIP => &GZIP_MAGIC,
+        LZ4 => &LZ4_MAGIC,
+        ZSTD => &ZSTD_MAGIC,
+        _ => return Ok(Cow::Borrowed(content)),
+    };
+
+    if !frame.starts_with(magic) {
+        return Ok(Cow::Borrowed(content));
+    }
+
+    let mut decompressed = Vec::new();
+
+    match header {
+        #[cfg(feature = "gzip")]
+        GZIP => {
+            use std::io::Read;
+            flate2::read::GzDecoder::new(frame).read_to_end(&mut decompressed)?;
+        }
+        #[cfg(feature = "lz4")]
+        LZ4 => {
+            use std::io::Read;
+            lz4_flex::frame::FrameDecoder::new(frame).read_to_end(&mut decompressed)?;
+        }
+        #[cfg(feature = "zstd")]
+        ZSTD => {
+            zstd::stream::copy_decode(frame, &mut decompressed)?;
+        }
+        _ => return Err(unsupported(header)),
+    }
+
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Represents a message [encoding](Encoding) that compresses the content of another [encoding](Encoding).
+///
+/// # Remarks
+///
+/// Compressed content is prefixed with a header that identifies the [compression](Compression) algorithm. Content
+/// without a recognized header is decoded as-is, which allows compressed and uncompressed content to coexist. Content
+/// compressed with any enabled algorithm can be decoded, regardless of the configured [compression](Compression).
+pub struct Compressed<E> {
+    encoding: E,
+    compression: Compression,
+}
+
+impl<E> Compressed<E> {
+    /// Initializes a new [Compressed] message encoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - the inner [encoding](Encoding) to compress
+    /// * `compression` - the [compression](Compression) algorithm to apply
+    pub fn new(encoding: E, compression: Compression) -> Self {
+        Self {
+            encoding,
+            compression,
+        }
+    }
+
+    /// Initializes a new [Compressed] message encoding using [gzip](Compression::Gzip).
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - the inner [encoding](Encoding) to compress
+    #[cfg(feature = "gzip")]
+    #[inline]
+    pub fn gzip(encoding: E) -> Self {
+        Self::new(encoding, Compression::Gzip)
+    }
+
+    /// Initializes a new [Compressed] message encoding using [LZ4](Compression::Lz4).
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - the inner [encoding](Encoding) to compress
+    #[cfg(feature = "lz4")]
+    #[inline]
+    pub fn lz4(encoding: E) -> Self {
+        Self::new(encoding, Compression::Lz4)
+    }
+
+    /// Initializes a new [Compressed] message encoding using [Zstandard](Compression::Zstd).
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - the inner [encoding](Encoding) to compress
+    #[cfg(feature = "zstd")]
+    #[inline]
+    pub fn zstd(encoding: E) -> Self {
+        Self::new(encoding, Compression::Zstd)
+    }
+
+    /// Gets the configured [compression](Compression) algorithm.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+}
+
+impl<T, E> Encoding<T> for Compressed<E>
+where
+    T: ?Sized + Sync,
+    E: Encoding<T>,
+{
+    fn schema(&self) -> &Schema {
+        self.encoding.schema()
+    }
+
+    fn encode(&self, message: &T) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        let content = self.encoding.encode(message)?;
+
+        self.compression
+            .compress(&content)
+            .map_err(|err| Box::new(err) as Box<dyn Error + Send>)
+    }
+
+    fn decode(&self, message: &[u8]) -> Result<Box<T>, Box<dyn Error + Send>> {
+        let content = decompress(message).map_err(|err| Box::new(err) as Box<dyn Error + Send>)?;
+        self.encoding.decode(&content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Raw(Schema);
+
+    impl Encoding<[u8]> for Raw {
+        fn schema(&self) -> &Schema {
+            &self.0
+        }
+
+        fn encode(&self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+            Ok(message.to_vec())
+        }
+
+        fn decode(&self, message: &[u8]) -> Result<Box<[u8]>, Box<dyn Error + Send>> {
+            Ok(message.into())
+        }
+    }
+
+    fn compressed(compression: Compression) -> Compressed<Raw> {
+        Compressed::new(Raw(Schema::initial("urn:test:raw")), compression)
+    }
+
+    fn all() -> Vec<Compression> {
+        vec![
+            #[cfg(feature = "gzip")]
+            Compression::Gzip,
+            #[cfg(feature = "lz4")]
+            Compression::Lz4,
+            #[cfg(feature = "zstd")]
+            Compression::Zstd,
+        ]
+    }
+
+    #[test]
+    fn content_should_roundtrip_with_compression() {
+        for compression in all() {
+            // arrange
+            let encoding = compressed(compression);
+            let expected = "The quick brown fox jumps over the lazy dog. ".repeat(64);
+
+            // act
+            let binary = encoding.encode(expected.as_bytes()).unwrap();
+            let actual = encoding.decode(&binary).unwrap();
+
+            // assert
+            assert!(
+                binary.len() < expected.len(),
+                "{compression:?} did not compress"
+            );
+            assert_eq!(binary[0], compression.header());
+            assert_eq!(&*actual, expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn uncompressed_content_should_be_decoded_as_is() {
+        for compression in all() {
+            // arrange
+            let encoding = compressed(compression);
+            let expected = br#"{"id":42,"name":"test"}"#;
+
+            // act
+            let actual = encoding.decode(expected).unwrap();
+
+            // assert
+            assert_eq!(&*actual, expected);
+        }
+    }
+
+    #[test]
+    fn content_should_decode_with_different_compression() {
+        let algorithms = all();
+
+        for from in &algorithms {
+            for to in &algorithms {
+                // arrange
+                let expected = b"content compressed with another algorithm";
+                let binary = compressed(*from).encode(expected).unwrap();
+
+                // act
+                let actual = compressed(*to).decode(&binary).unwrap();
+
+                // assert
+                assert_eq!(&*actual, expected);
+            }
+        }
+    }
+}
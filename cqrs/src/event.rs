@@ -1,14 +1,32 @@
+mod append;
 mod delete;
+mod direction;
+mod expected;
+mod head;
 mod message;
+mod page;
+mod policy;
 mod predicate;
+mod raw;
 mod receiver;
 mod store;
+mod token;
+mod unknown;
 
+pub use append::Append;
 pub use delete::Delete;
+pub use direction::Direction;
+pub use expected::ExpectedVersion;
+pub use head::Head;
 pub use message::Event;
+pub use page::IdPage;
+pub use policy::DecodePolicy;
 pub use predicate::{LoadOptions, Predicate, PredicateBuilder};
+pub use raw::RawEvent;
 pub use receiver::Receiver;
-pub use store::{EventStream, IdStream, Store, StoreError, StoreOptions};
+pub use store::{EventStream, IdStream, RawEventStream, Store, StoreError, StoreOptions};
+pub use token::ContinuationToken;
+pub use unknown::UnknownEvent;
 
 /// Creates and returns a new [event](Event) [transcoder](crate::message::Transcoder).
 #[inline]
@@ -0,0 +1,42 @@
+use super::Event;
+use crate::Version;
+use uuid::Uuid;
+
+/// Represents a collection of [events](Event) to append to a single stream.
+pub struct Append<'a, T = Uuid> {
+    id: T,
+    expected_version: Version,
+    events: &'a [Box<dyn Event>],
+}
+
+impl<'a, T> Append<'a, T> {
+    /// Initializes a new [Append].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the events to save
+    /// * `expected_version` - the current, expected [version](Version)
+    /// * `events` - the list of [events](Event) to save
+    pub fn new(id: T, expected_version: Version, events: &'a [Box<dyn Event>]) -> Self {
+        Self {
+            id,
+            expected_version,
+            events,
+        }
+    }
+
+    /// Gets the identifier of the events to save.
+    pub fn id(&self) -> &T {
+        &self.id
+    }
+
+    /// Gets the current, expected [version](Version).
+    pub fn expected_version(&self) -> Version {
+        self.expected_version
+    }
+
+    /// Gets the list of [events](Event) to save.
+    pub fn events(&self) -> &'a [Box<dyn Event>] {
+        self.events
+    }
+}
@@ -0,0 +1,24 @@
+/// Defines the possible directions in which events are read.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// Indicates events are read from the oldest to the newest.
+    #[default]
+    Forward,
+
+    /// Indicates events are read from the newest to the oldest.
+    Backward,
+}
+
+impl Direction {
+    /// Gets a value indicating whether events are read forward.
+    #[inline]
+    pub fn forward(&self) -> bool {
+        matches!(self, Direction::Forward)
+    }
+
+    /// Gets a value indicating whether events are read backward.
+    #[inline]
+    pub fn backward(&self) -> bool {
+        matches!(self, Direction::Backward)
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{Concurrency, Version};
+
+/// Defines the possible expectations of a stream when saving [events](super::Event).
+///
+/// # Remarks
+///
+/// An expectation other than [ExpectedVersion::Any] is always honored, regardless of the
+/// [concurrency](Concurrency) configured for a store. This makes it possible for a creation
+/// command to reliably fail when an identifier is reused.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ExpectedVersion {
+    /// Indicates the stream may be in any state and the events are appended to the end of it.
+    #[default]
+    Any,
+
+    /// Indicates the stream must not exist.
+    NoStream,
+
+    /// Indicates the stream must exist and the events are appended to the end of it.
+    StreamExists,
+
+    /// Indicates the stream must be at the specified [version](Version).
+    Exact(Version),
+}
+
+impl ExpectedVersion {
+    /// Gets the [concurrency](Concurrency) required to honor the expectation.
+    #[inline]
+    pub fn concurrency(&self) -> Concurrency {
+        match self {
+            Self::Any | Self::StreamExists => Concurrency::None,
+            Self::NoStream | Self::Exact(_) => Concurrency::Enforced,
+        }
+    }
+}
+
+impl From<Version> for ExpectedVersion {
+    fn from(value: Version) -> Self {
+        if value == Version::default() {
+            Self::NoStream
+        } else {
+            Self::Exact(value)
+        }
+    }
+}
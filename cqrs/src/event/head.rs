@@ -0,0 +1,112 @@
+use crate::Version;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Represents the head of an [event](super::Event) stream, which describes the stream without
+/// loading its events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Head<T = Uuid> {
+    id: T,
+    deleted: bool,
+    version: Version,
+    count: u64,
+    first_stored_on: Option<SystemTime>,
+    last_stored_on: Option<SystemTime>,
+}
+
+impl<T> Head<T> {
+    /// Initializes a new [Head] for an existing stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the stream
+    /// * `version` - the current [version](Version) of the stream
+    /// * `count` - the number of events in the stream
+    /// * `first_stored_on` - the [date](SystemTime) the first event was stored on
+    /// * `last_stored_on` - the [date](SystemTime) the last event was stored on
+    pub fn new(
+        id: T,
+        version: Version,
+        count: u64,
+        first_stored_on: SystemTime,
+        last_stored_on: SystemTime,
+    ) -> Self {
+        Self {
+            id,
+            deleted: false,
+            version,
+            count,
+            first_stored_on: Some(first_stored_on),
+            last_stored_on: Some(last_stored_on),
+        }
+    }
+
+    /// Initializes a new [Head] for a stream that does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the stream
+    pub fn empty(id: T) -> Self {
+        Self {
+            id,
+            deleted: false,
+            version: Version::default(),
+            count: 0,
+            first_stored_on: None,
+            last_stored_on: None,
+        }
+    }
+
+    /// Initializes a new [Head] for a stream that has been deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the stream
+    pub fn tombstone(id: T) -> Self {
+        Self {
+            deleted: true,
+            ..Self::empty(id)
+        }
+    }
+
+    /// Gets the identifier of the stream.
+    pub fn id(&self) -> &T {
+        &self.id
+    }
+
+    /// Gets a value indicating whether the stream exists.
+    #[inline]
+    pub fn exists(&self) -> bool {
+        self.count > 0
+    }
+
+    /// Gets a value indicating whether the stream has been deleted.
+    #[inline]
+    pub fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    /// Gets the current [version](Version) of the stream.
+    ///
+    /// # Remarks
+    ///
+    /// The version is the default [version](Version) when the stream does not exist.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Gets the number of events in the stream.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets the [date](SystemTime) the first event in the stream was stored on, if any.
+    pub fn first_stored_on(&self) -> Option<SystemTime> {
+        self.first_stored_on
+    }
+
+    /// Gets the [date](SystemTime) the last event in the stream was stored on, if any.
+    pub fn last_stored_on(&self) -> Option<SystemTime> {
+        self.last_stored_on
+    }
+}
@@ -0,0 +1,63 @@
+use uuid::Uuid;
+
+/// Represents a page of identifiers in an [event store](super::Store).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdPage<T = Uuid> {
+    ids: Vec<T>,
+    token: Option<T>,
+}
+
+impl<T> IdPage<T> {
+    /// Initializes a new [IdPage].
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - the identifiers in the page
+    /// * `token` - the token used to read the next page, if any
+    pub fn new(ids: Vec<T>, token: Option<T>) -> Self {
+        Self { ids, token }
+    }
+
+    /// Gets the identifiers in the page.
+    pub fn ids(&self) -> &[T] {
+        &self.ids
+    }
+
+    /// Gets the token used to read the next page, if any.
+    ///
+    /// # Remarks
+    ///
+    /// The token is the last identifier in the page. It can be persisted as a checkpoint and later
+    /// used to resume reading from the next page. There are no more pages when there is no token.
+    pub fn token(&self) -> Option<&T> {
+        self.token.as_ref()
+    }
+}
+
+impl<T: Clone> IdPage<T> {
+    /// Initializes a new [IdPage] from the identifiers read for the specified limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - the identifiers in the page
+    /// * `limit` - the maximum number of identifiers requested for the page
+    ///
+    /// # Remarks
+    ///
+    /// A page that is not full is the last page and has no token.
+    pub fn of(ids: Vec<T>, limit: usize) -> Self {
+        let token = if ids.len() < limit {
+            None
+        } else {
+            ids.last().cloned()
+        };
+
+        Self::new(ids, token)
+    }
+}
+
+impl<T> From<IdPage<T>> for Vec<T> {
+    fn from(value: IdPage<T>) -> Self {
+        value.ids
+    }
+}
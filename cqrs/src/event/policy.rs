@@ -0,0 +1,21 @@
+/// Defines the possible behaviors when a stored event cannot be decoded.
+///
+/// # Remarks
+///
+/// A lenient policy allows older readers to continue loading streams that contain events written by
+/// a newer version of a service, such as during a rolling deployment. An
+/// [aggregate](crate::Aggregate) that replays [delivered](DecodePolicy::Deliver) events must define
+/// a catch-all handler for events it does not know.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DecodePolicy {
+    /// Indicates that loading fails when an event cannot be decoded.
+    #[default]
+    Fail,
+
+    /// Indicates that events which cannot be decoded are skipped.
+    Skip,
+
+    /// Indicates that events which cannot be decoded are delivered as
+    /// [unknown events](super::UnknownEvent).
+    Deliver,
+}
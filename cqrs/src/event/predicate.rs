@@ -1,3 +1,4 @@
+use super::{ContinuationToken, Direction};
 use crate::message::Schema;
 use crate::{Range, Version};
 use std::{
@@ -39,8 +40,38 @@ pub struct Predicate<'a, T: Debug + Send = Uuid> {
     /// Gets or sets the [date](SystemTime) [range](Range) to apply to a predicate, if any.
     pub stored_on: Range<SystemTime>,
 
+    /// Gets or sets the correlation identifier to apply to a predicate, if any.
+    pub correlation_id: Option<&'a str>,
+
     /// Gets or sets the associated [load options](LoadOptions).
     pub load: LoadOptions,
+
+    /// Gets or sets the maximum number of events to read, if any.
+    pub limit: Option<usize>,
+
+    /// Gets or sets the [direction](Direction) in which events are read.
+    pub direction: Direction,
+
+    /// Gets or sets the [continuation token](ContinuationToken) to read after, if any.
+    ///
+    /// # Remarks
+    ///
+    /// When the predicate has an identifier, only the [version](Version) of the token is used.
+    pub after: Option<&'a ContinuationToken<T>>,
+}
+
+impl<T: Debug + Send> Predicate<'_, T> {
+    /// Gets a value indicating whether the predicate reads a page of events.
+    ///
+    /// # Remarks
+    ///
+    /// A page is read when a limit, a [continuation token](ContinuationToken), or the
+    /// [backward](Direction::Backward) direction is specified. Snapshots are never loaded for a
+    /// page because they replace the events that precede them.
+    #[inline]
+    pub fn paged(&self) -> bool {
+        self.limit.is_some() || self.after.is_some() || self.direction.backward()
+    }
 }
 
 impl<'a, T: Debug + Send> Predicate<'a, T> {
@@ -55,7 +86,11 @@ impl<'a, T: Debug + Send> Predicate<'a, T> {
             version: Unbounded,
             types: Default::default(),
             stored_on: Default::default(),
+            correlation_id: None,
             load: Default::default(),
+            limit: None,
+            direction: Default::default(),
+            after: None,
         }
     }
 }
@@ -114,6 +149,16 @@ impl<'a, T: Debug + Send> PredicateBuilder<'a, T> {
         self
     }
 
+    /// Sets the correlation identifier of events to apply to a predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the correlation identifier
+    pub fn correlation_id(mut self, value: &'a str) -> Self {
+        self.0.correlation_id = Some(value);
+        self
+    }
+
     /// Sets the load options applied to a predicate.
     ///
     /// # Arguments
@@ -124,6 +169,36 @@ impl<'a, T: Debug + Send> PredicateBuilder<'a, T> {
         self
     }
 
+    /// Sets the maximum number of events to read.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the maximum number of events
+    pub fn limit(mut self, value: usize) -> Self {
+        self.0.limit = Some(value);
+        self
+    }
+
+    /// Sets the [direction](Direction) in which events are read.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the read [direction](Direction)
+    pub fn direction(mut self, value: Direction) -> Self {
+        self.0.direction = value;
+        self
+    }
+
+    /// Sets the [continuation token](ContinuationToken) that events are read after.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the [continuation token](ContinuationToken) of the last event read
+    pub fn after(mut self, value: &'a ContinuationToken<T>) -> Self {
+        self.0.after = Some(value);
+        self
+    }
+
     /// Merges an existing predicate into the current builder.
     ///
     /// # Arguments
@@ -142,10 +217,26 @@ impl<'a, T: Debug + Send> PredicateBuilder<'a, T> {
         self.0.stored_on = predicate.stored_on.clone();
         self.0.types.extend(predicate.types.iter().cloned());
 
+        if let Some(value) = predicate.correlation_id {
+            self.0.correlation_id = Some(value);
+        }
+
         if !predicate.load.snapshots {
             self.0.load.snapshots = false;
         }
 
+        if let Some(value) = predicate.limit {
+            self.0.limit = Some(value);
+        }
+
+        if predicate.direction.backward() {
+            self.0.direction = predicate.direction;
+        }
+
+        if let Some(value) = predicate.after {
+            self.0.after = Some(value);
+        }
+
         self
     }
 
@@ -0,0 +1,48 @@
+use crate::message::Descriptor;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Represents a raw, stored [event](super::Event) that has not been decoded.
+pub struct RawEvent<T = Uuid> {
+    id: T,
+    stored_on: SystemTime,
+    descriptor: Descriptor,
+}
+
+impl<T> RawEvent<T> {
+    /// Initializes a new [RawEvent].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier the event belongs to
+    /// * `stored_on` - the [date](SystemTime) the event was stored on
+    /// * `descriptor` - the [descriptor](Descriptor) of the stored event
+    pub fn new(id: T, stored_on: SystemTime, descriptor: Descriptor) -> Self {
+        Self {
+            id,
+            stored_on,
+            descriptor,
+        }
+    }
+
+    /// Gets the identifier the event belongs to.
+    pub fn id(&self) -> &T {
+        &self.id
+    }
+
+    /// Gets the [date](SystemTime) the event was stored on.
+    pub fn stored_on(&self) -> SystemTime {
+        self.stored_on
+    }
+
+    /// Gets the [descriptor](Descriptor) of the stored event.
+    pub fn descriptor(&self) -> &Descriptor {
+        &self.descriptor
+    }
+}
+
+impl<T> From<RawEvent<T>> for Descriptor {
+    fn from(value: RawEvent<T>) -> Self {
+        value.descriptor
+    }
+}
@@ -1,9 +1,14 @@
-use super::{Event, Predicate};
+use super::{
+    Append, DecodePolicy, Event, ExpectedVersion, Head, IdPage, Predicate, RawEvent, UnknownEvent,
+};
 use crate::{
-    Clock, Concurrency, Mask, Range, StoreOptionsBuilder, Version, event::Delete, message::{EncodingError, Saved, Transcoder}, snapshot
+    Clock, Concurrency, Mask, Range, StoreOptionsBuilder, Version,
+    event::Delete,
+    message::{Descriptor, EncodingError, Saved, Schema, Transcoder},
+    snapshot,
 };
 use async_trait::async_trait;
-use futures::Stream;
+use futures::{Stream, future::ready, stream::once};
 use std::{error::Error, fmt::Debug, pin::Pin, sync::Arc, time::SystemTime};
 use thiserror::Error;
 use uuid::Uuid;
@@ -15,6 +20,10 @@ pub type IdStream<T> = Pin<Box<dyn Stream<Item = Result<T, StoreError<T>>> + Sen
 pub type EventStream<'a, T> =
     Pin<Box<dyn Stream<Item = Result<Saved<Box<dyn Event>>, StoreError<T>>> + Send + 'a>>;
 
+/// Represents a stored [raw event](RawEvent) [stream](Stream).
+pub type RawEventStream<'a, T> =
+    Pin<Box<dyn Stream<Item = Result<RawEvent<T>, StoreError<T>>> + Send + 'a>>;
+
 /// Defines the behavior of an event store.
 #[async_trait]
 pub trait Store<T: Debug + Send = Uuid>: Send + Sync {
@@ -34,6 +43,33 @@ pub trait Store<T: Debug + Send = Uuid>: Send + Sync {
     /// event recorded within the range.
     async fn ids(&self, stored_on: Range<SystemTime>) -> IdStream<T>;
 
+    /// Reads a page of the unique identifiers in the store.
+    ///
+    /// # Arguments
+    ///
+    /// * `stored_on` - the [date](SystemTime) [range](Range) used to filter results
+    /// * `limit` - the maximum number of identifiers in the page
+    /// * `after` - the [token](IdPage::token) of the previous page, if any
+    ///
+    /// # Remarks
+    ///
+    /// Unlike [Store::ids], each page is read independently so that a long-running job can
+    /// checkpoint the token of the last page it processed and later resume from it. The order of
+    /// the identifiers is defined by the store. A store is not required to support paging
+    /// identifiers, in which case [StoreError::Unsupported] is returned.
+    #[allow(unused_variables)]
+    async fn ids_page(
+        &self,
+        stored_on: Range<SystemTime>,
+        limit: usize,
+        after: Option<&T>,
+    ) -> Result<IdPage<T>, StoreError<T>>
+    where
+        T: Sync,
+    {
+        Err(StoreError::Unsupported)
+    }
+
     /// Loads a sequence of [events](Event).
     ///
     /// # Arguments
@@ -41,6 +77,48 @@ pub trait Store<T: Debug + Send = Uuid>: Send + Sync {
     /// * `predicate` - the optional [predicate](Predicate) used to filter events
     async fn load<'a>(&self, predicate: Option<&'a Predicate<'a, T>>) -> EventStream<'a, T>;
 
+    /// Loads a sequence of [raw events](RawEvent) without decoding them.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - the optional [predicate](Predicate) used to filter events
+    ///
+    /// # Remarks
+    ///
+    /// Raw events are intended for tooling, such as administration or diagnostics, that must read
+    /// events whose schemas have not been registered in the configured [transcoder](Transcoder).
+    /// Snapshots are never included in the results. A store is not required to support loading raw
+    /// events, in which case the stream yields [StoreError::Unsupported].
+    #[allow(unused_variables)]
+    async fn load_raw<'a>(&self, predicate: Option<&'a Predicate<'a, T>>) -> RawEventStream<'a, T>
+    where
+        T: Sync,
+    {
+        Box::pin(once(ready(Err(StoreError::Unsupported))))
+    }
+
+    /// Gets the [head](Head) of an event stream without loading its events.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the stream
+    ///
+    /// # Remarks
+    ///
+    /// The head is intended for cheaply validating a command, such as whether the stream exists
+    /// or what its current [version](Version) is. A stream that does not exist yields an
+    /// [empty](Head::empty) head rather than an error. A store that permanently removes deleted
+    /// events may not be able to distinguish a deleted stream from one that never existed. A store
+    /// is not required to support reading the head of a stream, in which case
+    /// [StoreError::Unsupported] is returned.
+    #[allow(unused_variables)]
+    async fn head(&self, id: &T) -> Result<Head<T>, StoreError<T>>
+    where
+        T: Sync,
+    {
+        Err(StoreError::Unsupported)
+    }
+
     /// Saves a collection of events and returns the new [version](Version), if any.
     ///
     /// # Arguments
@@ -55,6 +133,82 @@ pub trait Store<T: Debug + Send = Uuid>: Send + Sync {
         events: &[Box<dyn Event>],
     ) -> Result<Version, StoreError<T>>;
 
+    /// Saves a collection of events with an explicit expectation of the stream and returns the new
+    /// [version](Version), if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the events to save
+    /// * `expected` - the [expected version](ExpectedVersion) of the stream
+    /// * `events` - the list of [events](Event) to save
+    ///
+    /// # Remarks
+    ///
+    /// Unlike [Store::save], the expectation is honored regardless of the configured
+    /// [concurrency](Concurrency). If the stream exists when it is expected not to or is not at the
+    /// expected [version](Version), [StoreError::Conflict] is returned. If the stream does not exist
+    /// when it is expected to, [StoreError::NotFound] is returned. A store is not required to
+    /// support explicit expectations, in which case [StoreError::Unsupported] is returned.
+    #[allow(unused_variables)]
+    async fn save_expecting(
+        &self,
+        id: &T,
+        expected: ExpectedVersion,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<T>> {
+        Err(StoreError::Unsupported)
+    }
+
+    /// Saves a collection of events at most once for a message and returns the new
+    /// [version](Version), if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the events to save
+    /// * `expected_version` - the current, expected [version](Version)
+    /// * `events` - the list of [events](Event) to save
+    /// * `message_id` - the identifier of the message, such as a command, that produced the events
+    ///
+    /// # Remarks
+    ///
+    /// The message identifier is recorded with the saved events and must be unique per stream. If
+    /// events have already been saved for the message, no events are appended and the
+    /// [version](Version) previously assigned to them is returned instead. This makes it safe to
+    /// retry the handling of a message that may or may not have been saved. A store is not required
+    /// to support idempotent saves, in which case [StoreError::Unsupported] is returned.
+    #[allow(unused_variables)]
+    async fn save_once(
+        &self,
+        id: &T,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: &str,
+    ) -> Result<Version, StoreError<T>> {
+        Err(StoreError::Unsupported)
+    }
+
+    /// Saves collections of events for one or more identifiers as a single, atomic operation and
+    /// returns the new [versions](Version).
+    ///
+    /// # Arguments
+    ///
+    /// * `appends` - the list of [appends](Append) to save
+    ///
+    /// # Remarks
+    ///
+    /// Either all of the events are saved or none of them are. If any of the appends results in an
+    /// error, such as a concurrency [conflict](StoreError::Conflict), the entire operation fails.
+    /// The returned [versions](Version) are in the same order as the provided appends. A store is
+    /// not required to support atomic saves across identifiers, in which case
+    /// [StoreError::Unsupported] is returned.
+    #[allow(unused_variables)]
+    async fn save_all(&self, appends: &[Append<'_, T>]) -> Result<Vec<Version>, StoreError<T>>
+    where
+        T: Sync,
+    {
+        Err(StoreError::Unsupported)
+    }
+
     /// Deletes a collection of events.
     ///
     /// # Arguments
@@ -81,6 +235,7 @@ pub trait Store<T: Debug + Send = Uuid>: Send + Sync {
 pub struct StoreOptions<ID> {
     concurrency: Concurrency,
     delete: Delete,
+    decode: DecodePolicy,
     mask: Option<Arc<dyn Mask>>,
     pub(crate) clock: Arc<dyn Clock>,
     transcoder: Arc<Transcoder<dyn Event>>,
@@ -98,6 +253,11 @@ impl<ID> StoreOptions<ID> {
     /// * `clock` - the associated [clock](Clock)
     /// * `transcoder` - the associated [transcoder](Transcoder)
     /// * `snapshots` - the associated [snapshot store](snapshot::Store)
+    ///
+    /// # Remarks
+    ///
+    /// Events that cannot be decoded [fail](DecodePolicy::Fail) by default. Use
+    /// [set_decode_policy](Self::set_decode_policy) to apply a different policy.
     pub fn new(
         concurrency: Concurrency,
         delete: Delete,
@@ -109,6 +269,7 @@ impl<ID> StoreOptions<ID> {
         Self {
             concurrency,
             delete,
+            decode: Default::default(),
             mask,
             clock,
             transcoder,
@@ -133,6 +294,22 @@ impl<ID> StoreOptions<ID> {
         self.delete
     }
 
+    /// Gets the configured [decode policy](DecodePolicy).
+    #[inline]
+    pub fn decode_policy(&self) -> DecodePolicy {
+        self.decode
+    }
+
+    /// Sets the [decode policy](DecodePolicy) for events that cannot be decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the [decode policy](DecodePolicy) to apply
+    #[inline]
+    pub fn set_decode_policy(&mut self, value: DecodePolicy) {
+        self.decode = value;
+    }
+
     /// Gets the configured [mask](Mask), if any.
     pub fn mask(&self) -> Option<&(dyn Mask + 'static)> {
         self.mask.as_deref()
@@ -152,6 +329,38 @@ impl<ID> StoreOptions<ID> {
     pub fn snapshots(&self) -> Option<&dyn snapshot::Store<ID>> {
         self.snapshots.as_deref()
     }
+
+    /// Decodes a stored event using the configured [transcoder](Transcoder) and
+    /// [decode policy](DecodePolicy).
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - the [schema](Schema) of the stored event
+    /// * `version` - the [version](Version) of the stored event
+    /// * `content` - the content of the stored event
+    ///
+    /// # Returns
+    ///
+    /// The decoded [event](Event), if any. No event is returned when the stored event cannot be
+    /// decoded and the [decode policy](DecodePolicy) is to [skip](DecodePolicy::Skip) it.
+    pub fn decode(
+        &self,
+        schema: &Schema,
+        version: Version,
+        content: &[u8],
+    ) -> Result<Option<Box<dyn Event>>, EncodingError> {
+        match self.transcoder.decode(schema, content) {
+            Ok(event) => Ok(Some(event)),
+            Err(error) => match self.decode {
+                DecodePolicy::Fail => Err(error),
+                DecodePolicy::Skip => Ok(None),
+                DecodePolicy::Deliver => {
+                    let descriptor = Descriptor::new(schema.clone(), version, content.to_vec());
+                    Ok(Some(Box::new(UnknownEvent::new(descriptor))))
+                }
+            },
+        }
+    }
 }
 
 impl<ID> From<&StoreOptions<ID>> for Arc<dyn Clock> {
@@ -171,6 +380,10 @@ pub enum StoreError<T: Debug + Send> {
     #[error("the item with identifier {0:?} has been deleted")]
     Deleted(T),
 
+    /// Indicates the specified identifier was not found.
+    #[error("the item with identifier {0:?} was not found")]
+    NotFound(T),
+
     /// Indicates an invalid store [encoding](Encoding).
     #[error(transparent)]
     InvalidEncoding(#[from] EncodingError),
@@ -205,6 +418,7 @@ impl<T: Debug + PartialEq + Send> PartialEq for StoreError<T> {
         match (self, other) {
             (Self::Conflict(l0, l1), Self::Conflict(r0, r1)) => l0 == r0 && l1 == r1,
             (Self::Deleted(l0), Self::Deleted(r0)) => l0 == r0,
+            (Self::NotFound(l0), Self::NotFound(r0)) => l0 == r0,
             (Self::InvalidEncoding(l0), Self::InvalidEncoding(r0)) => l0 == r0,
             (Self::BatchTooLarge(l0), Self::BatchTooLarge(r0)) => l0 == r0,
             (Self::Unknown(_), Self::Unknown(_)) => false,
@@ -0,0 +1,46 @@
+use super::RawEvent;
+use crate::Version;
+use uuid::Uuid;
+
+/// Represents a token used to continue reading [events](super::Event) after the last event read.
+///
+/// # Remarks
+///
+/// A token is the position of an event in a store. A read that continues from a token begins with
+/// the next event in the direction being read, excluding the event the token was created from.
+/// A decoded [event](super::Event) does not carry its identifier so a read across identifiers
+/// should use [load_raw](super::Store::load_raw) and create the token from the last
+/// [raw event](RawEvent) read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContinuationToken<T = Uuid> {
+    id: T,
+    version: Version,
+}
+
+impl<T> ContinuationToken<T> {
+    /// Initializes a new [ContinuationToken].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the last event read
+    /// * `version` - the [version](Version) of the last event read
+    pub fn new(id: T, version: Version) -> Self {
+        Self { id, version }
+    }
+
+    /// Gets the identifier of the last event read.
+    pub fn id(&self) -> &T {
+        &self.id
+    }
+
+    /// Gets the [version](Version) of the last event read.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+}
+
+impl<T: Clone> From<&RawEvent<T>> for ContinuationToken<T> {
+    fn from(value: &RawEvent<T>) -> Self {
+        Self::new(value.id().clone(), value.descriptor().version)
+    }
+}
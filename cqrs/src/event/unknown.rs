@@ -0,0 +1,52 @@
+use super::Event;
+use crate::message::{Descriptor, Message, Schema};
+use std::any::Any;
+
+/// Represents a stored [event](Event) that could not be decoded.
+///
+/// # Remarks
+///
+/// An unknown event is only produced when a store is configured to
+/// [deliver](super::DecodePolicy::Deliver) events that cannot be decoded; for example, an event
+/// revision written by a newer version of a service.
+pub struct UnknownEvent {
+    descriptor: Descriptor,
+}
+
+impl UnknownEvent {
+    /// Initializes a new [UnknownEvent].
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - the [descriptor](Descriptor) of the stored event
+    pub fn new(descriptor: Descriptor) -> Self {
+        Self { descriptor }
+    }
+
+    /// Gets the [descriptor](Descriptor) of the stored event.
+    pub fn descriptor(&self) -> &Descriptor {
+        &self.descriptor
+    }
+}
+
+impl Message for UnknownEvent {
+    fn schema(&self) -> Schema {
+        self.descriptor.schema.clone()
+    }
+}
+
+impl Event for UnknownEvent {
+    fn name(&self) -> &str {
+        self.descriptor.schema.kind()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl From<UnknownEvent> for Descriptor {
+    fn from(value: UnknownEvent) -> Self {
+        value.descriptor
+    }
+}
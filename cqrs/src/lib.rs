@@ -6,6 +6,7 @@ mod mask;
 mod migration;
 mod range;
 mod repository;
+mod unit_of_work;
 mod version;
 
 pub use aggregate::{Aggregate, ChangeSet, EventHistory};
@@ -16,6 +17,7 @@ pub use mask::{Mask, SecureMask};
 pub use migration::{StoreMigration, StoreMigrator};
 pub use range::Range;
 pub use repository::{Repository, RepositoryError};
+pub use unit_of_work::UnitOfWork;
 pub use version::Version;
 
 /// Contains support for commands.
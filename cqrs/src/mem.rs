@@ -1,6 +1,9 @@
 use crate::{
-    Clock, Mask, Range, Version,
-    event::{self, Event, EventStream, IdStream, Predicate, StoreError},
+    Clock, Concurrency, Mask, Range, Version,
+    event::{
+        self, Append, Event, EventStream, ExpectedVersion, Head, IdPage, IdStream, Predicate,
+        RawEvent, RawEventStream, StoreError,
+    },
     message::{Descriptor, Saved, Schema},
     snapshot::{self, Retention, Snapshot, SnapshotError},
 };
@@ -10,6 +13,7 @@ use futures::{
     stream::{self, once},
 };
 use std::{
+    cmp::Ordering,
     collections::HashMap,
     fmt::Debug,
     hash::Hash,
@@ -70,7 +74,10 @@ impl EncodedVersion for Version {
 struct Row {
     schema: Schema,
     version: Version,
+    stored_on: SystemTime,
     data: Vec<u8>,
+    message_id: Option<String>,
+    correlation_id: Option<String>,
 }
 
 /// Represents an in-memory [snapshot store](snapshot::Store).
@@ -180,11 +187,14 @@ impl<T: Clone + Debug + Eq + Hash + Send + Sync> snapshot::Store<T> for Snapshot
         let row = Row {
             schema: snapshot.schema(),
             version,
+            stored_on: self.options.clock().now(),
             data: self
                 .options
                 .transcoder()
                 .encode(&*snapshot)
                 .map_err(SnapshotError::InvalidEncoding)?,
+            message_id: None,
+            correlation_id: None,
         };
         let _ = table.insert(id.clone(), row);
 
@@ -198,9 +208,16 @@ impl<T: Clone + Debug + Eq + Hash + Send + Sync> snapshot::Store<T> for Snapshot
 }
 
 /// Represents an in-memory [event store](event::Store).
+///
+/// # Remarks
+///
+/// Paged reads require a stable order of identifiers. A store created with
+/// [ordered](EventStore::ordered) supports paged reads, while any other store returns
+/// [StoreError::Unsupported] when a page is requested.
 pub struct EventStore<ID = Uuid> {
     table: RwLock<HashMap<ID, Vec<Vec<Row>>>>,
     options: event::StoreOptions<ID>,
+    order: Option<fn(&ID, &ID) -> Ordering>,
 }
 
 impl<ID: Clone + Debug + Eq + Hash + Send + Sync> EventStore<ID> {
@@ -213,6 +230,7 @@ impl<ID: Clone + Debug + Eq + Hash + Send + Sync> EventStore<ID> {
         Self {
             table: Default::default(),
             options,
+            order: None,
         }
     }
 
@@ -234,6 +252,7 @@ impl<ID: Clone + Debug + Eq + Hash + Send + Sync> EventStore<ID> {
         if let Some(snapshots) = self.options.snapshots().as_deref()
             && let Some(predicate) = predicate
             && predicate.load.snapshots
+            && !predicate.paged()
             && let Some(id) = predicate.id
         {
             let predicate = Some(predicate.into());
@@ -242,6 +261,140 @@ impl<ID: Clone + Debug + Eq + Hash + Send + Sync> EventStore<ID> {
 
         Ok(None)
     }
+
+    fn append(
+        &self,
+        id: &ID,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: Option<&str>,
+    ) -> Result<Version, StoreError<ID>> {
+        let mut table = self.table.write().unwrap();
+        let concurrency = self.options.concurrency();
+        self.write(
+            &mut table,
+            id,
+            expected_version,
+            events,
+            message_id,
+            concurrency,
+        )
+    }
+
+    fn write(
+        &self,
+        table: &mut HashMap<ID, Vec<Vec<Row>>>,
+        id: &ID,
+        mut expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: Option<&str>,
+        concurrency: Concurrency,
+    ) -> Result<Version, StoreError<ID>> {
+        if events.is_empty() {
+            return Ok(expected_version);
+        }
+
+        if expected_version != Version::default()
+            && let Some(mask) = self.options.mask()
+        {
+            expected_version = expected_version.unmask(mask);
+        }
+
+        if expected_version.invalid() {
+            return Err(StoreError::InvalidVersion);
+        }
+
+        if events.len() > <Version as EncodedVersion>::max().sequence() as usize {
+            return Err(StoreError::BatchTooLarge(
+                <Version as EncodedVersion>::max().sequence(),
+            ));
+        }
+
+        // a deleted stream is retained without any rows as a tombstone
+        if let Some(rows) = table.get(id)
+            && !rows.is_empty()
+        {
+            if let Some(message_id) = message_id
+                && let Some(mut version) = rows
+                    .iter()
+                    .find(|rows| rows[0].message_id.as_deref() == Some(message_id))
+                    .and_then(|rows| rows.last())
+                    .map(|row| row.version)
+            {
+                if let Some(mask) = self.options.mask() {
+                    version = version.mask(mask);
+                }
+
+                return Ok(version);
+            }
+
+            let count = rows.len();
+
+            if count > expected_version.number() as usize {
+                if concurrency.enforced() {
+                    return Err(StoreError::Conflict(id.clone(), expected_version.number()));
+                } else {
+                    expected_version = new_version(count as u32);
+                }
+            }
+        } else if expected_version.number() > 1 {
+            return Err(StoreError::Deleted(id.clone()));
+        }
+
+        let mut version = expected_version.next_version();
+        let mut rows = Vec::new();
+        let stored_on = self.options.clock().now();
+
+        for event in events {
+            let row = Row {
+                schema: event.schema(),
+                version,
+                stored_on,
+                data: self
+                    .options
+                    .transcoder()
+                    .encode(event.as_ref())
+                    .map_err(StoreError::InvalidEncoding)?,
+                message_id: if rows.is_empty() {
+                    message_id.map(Into::into)
+                } else {
+                    None
+                },
+                correlation_id: event.correlation_id().map(Into::into),
+            };
+
+            rows.push(row);
+            version = version.next_sequence();
+        }
+
+        version = rows.last().unwrap().version;
+        let mut rows = vec![rows];
+
+        table
+            .entry(id.clone())
+            .and_modify(|row| row.append(&mut rows))
+            .or_insert(rows);
+
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
+        }
+
+        Ok(version)
+    }
+}
+
+impl<ID: Clone + Debug + Eq + Hash + Ord + Send + Sync> EventStore<ID> {
+    /// Initializes a new in-memory [EventStore] that orders identifiers to support paged reads.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the [store options](event::StoreOptions)
+    pub fn ordered(options: event::StoreOptions<ID>) -> Self {
+        Self {
+            order: Some(ID::cmp),
+            ..Self::new(options)
+        }
+    }
 }
 
 #[inline]
@@ -264,7 +417,10 @@ fn less_than_or_equal(bound: &Bound<SystemTime>, now: &SystemTime) -> bool {
 
 fn by<T: Debug + Send>(row: &Row, now: SystemTime, option: Option<&Predicate<T>>) -> bool {
     if let Some(predicate) = option {
-        if predicate.types.is_empty() || predicate.types.contains(&row.schema) {
+        if (predicate.types.is_empty() || predicate.types.contains(&row.schema))
+            && (predicate.correlation_id.is_none()
+                || predicate.correlation_id == row.correlation_id.as_deref())
+        {
             greater_than_or_equal(&predicate.stored_on.from, &now)
                 && less_than_or_equal(&predicate.stored_on.to, &now)
         } else {
@@ -311,6 +467,46 @@ fn select_version<T: Debug + Send>(
     }
 }
 
+fn paginate<T: Debug + Send>(
+    mut rows: Vec<(T, Row)>,
+    predicate: &Predicate<'_, T>,
+    mask: Option<&(dyn Mask + 'static)>,
+    order: fn(&T, &T) -> Ordering,
+) -> Vec<(T, Row)> {
+    // the table is not ordered so order rows by identifier; the sort is stable, which keeps the
+    // rows of an identifier ordered by version
+    rows.sort_by(|(left, _), (right, _)| order(left, right));
+
+    if let Some(token) = predicate.after {
+        let id = predicate.id.unwrap_or(token.id());
+        let mut version = token.version();
+
+        if let Some(mask) = mask {
+            version = version.unmask(mask);
+        }
+
+        // the token position need not exist so split at where it would be, which continues at
+        // the next identifier if the token identifier has no rows
+        if predicate.direction.backward() {
+            let split = rows.partition_point(|(other, row)| {
+                order(other, id).then(row.version.cmp(&version)).is_lt()
+            });
+            rows.truncate(split);
+        } else {
+            let split = rows.partition_point(|(other, row)| {
+                order(other, id).then(row.version.cmp(&version)).is_le()
+            });
+            let _ = rows.drain(..split);
+        }
+    }
+
+    if predicate.direction.backward() {
+        rows.reverse();
+    }
+
+    rows
+}
+
 #[async_trait]
 impl<T: Clone + Debug + Eq + Hash + Send + Sync + 'static> event::Store<T> for EventStore<T> {
     fn clock(&self) -> Arc<dyn Clock> {
@@ -319,12 +515,45 @@ impl<T: Clone + Debug + Eq + Hash + Send + Sync + 'static> event::Store<T> for E
 
     async fn ids(&self, _stored_on: Range<SystemTime>) -> IdStream<T> {
         let table = self.table.read().unwrap();
-        let ids: Vec<_> = table.keys().cloned().map(Ok).collect();
+        let ids: Vec<_> = table
+            .iter()
+            .filter(|(_, rows)| !rows.is_empty())
+            .map(|(id, _)| Ok(id.clone()))
+            .collect();
 
         Box::pin(stream::iter(ids))
     }
 
+    async fn ids_page(
+        &self,
+        _stored_on: Range<SystemTime>,
+        limit: usize,
+        after: Option<&T>,
+    ) -> Result<IdPage<T>, StoreError<T>> {
+        let Some(order) = self.order else {
+            return Err(StoreError::Unsupported);
+        };
+        let table = self.table.read().unwrap();
+        let mut ids: Vec<_> = table
+            .iter()
+            .filter(|(_, rows)| !rows.is_empty())
+            .map(|(id, _)| id)
+            .filter(|id| after.is_none_or(|after| order(id, after).is_gt()))
+            .collect();
+
+        // the table is not ordered so sort the identifiers to page in a stable order that
+        // continues after the previous page even if its last identifier has since been deleted
+        ids.sort_unstable_by(|left, right| order(left, right));
+        ids.truncate(limit);
+
+        Ok(IdPage::of(ids.into_iter().cloned().collect(), limit))
+    }
+
     async fn load<'a>(&self, predicate: Option<&'a Predicate<'a, T>>) -> EventStream<'a, T> {
+        if self.order.is_none() && predicate.is_some_and(Predicate::paged) {
+            return Box::pin(once(ready(Err(StoreError::Unsupported))));
+        }
+
         let snapshot = match self.get_snapshot(predicate).await {
             Ok(snapshot) => snapshot,
             Err(error) => {
@@ -335,189 +564,288 @@ impl<T: Clone + Debug + Eq + Hash + Send + Sync + 'static> event::Store<T> for E
         let option = Arc::new(predicate);
 
         if let Some(predicate) = option.as_deref() {
+            let options = self.options.clone();
+            let now = options.clock().now();
+            let mask = options.mask();
+            let limit = predicate.limit.unwrap_or(usize::MAX);
+            let mut rows = Vec::new();
+
             if let Some(id) = predicate.id {
-                if let Some(rows) = table.get(id) {
-                    let options = self.options.clone();
-                    let now = options.clock().now();
-                    let mask = options.mask();
-                    let mut rows =
-                        if let Some(index) = select_version(snapshot.as_ref(), predicate, mask) {
-                            rows.iter().skip(index).cloned().collect()
-                        } else {
-                            rows.clone()
-                        };
+                if let Some(batches) = table.get(id) {
+                    let index =
+                        select_version(snapshot.as_ref(), predicate, mask).unwrap_or_default();
 
                     if let Some(snapshot) = snapshot {
-                        rows.insert(
-                            0,
-                            vec![Row {
+                        rows.push((
+                            id.clone(),
+                            Row {
                                 schema: snapshot.schema,
                                 version: snapshot.version,
+                                stored_on: now,
                                 data: snapshot.content,
-                            }],
-                        );
+                                message_id: None,
+                                correlation_id: None,
+                            },
+                        ));
                     }
 
-                    Box::pin(stream::iter(
-                        rows.into_iter()
+                    rows.extend(
+                        batches
+                            .iter()
+                            .skip(index)
                             .flatten()
-                            .filter(move |row| by(row, now, option.as_deref()))
-                            .map(move |row| {
-                                let mut version = row.version;
-
-                                if let Some(mask) = options.mask() {
-                                    version = version.mask(mask);
-                                }
-
-                                Ok(Saved::new(
-                                    options
-                                        .transcoder()
-                                        .decode(&row.schema, &row.data)
-                                        .map_err(StoreError::InvalidEncoding)?,
-                                    version,
-                                ))
-                            }),
-                    ))
-                } else {
-                    Box::pin(stream::iter(std::iter::empty()))
+                            .map(|row| (id.clone(), row.clone())),
+                    );
                 }
             } else {
-                let options = self.options.clone();
-                let now = options.clock().now();
-                let version = match predicate.version {
-                    Included(version) => Some(version),
-                    Excluded(version) => Some(version),
-                    _ => None,
+                let index = match predicate.version {
+                    Included(version) => version.number() as usize,
+                    Excluded(version) => version.number() as usize,
+                    _ => 0,
                 };
-                let rows: Vec<_> = if let Some(version) = version {
-                    let index = version.number() as usize;
-                    let mut filtered = Vec::new();
 
-                    for rows in table.values() {
-                        filtered.extend(rows.iter().skip(index).cloned());
-                    }
+                for (id, batches) in table.iter() {
+                    rows.extend(
+                        batches
+                            .iter()
+                            .skip(index)
+                            .flatten()
+                            .map(|row| (id.clone(), row.clone())),
+                    );
+                }
+            }
 
-                    filtered
-                } else {
-                    table.values().flatten().cloned().collect()
-                };
+            if let Some(order) = self.order {
+                rows = paginate(rows, predicate, mask, order);
+            }
 
-                Box::pin(stream::iter(
-                    rows.into_iter()
-                        .flatten()
-                        .filter(move |row| by(row, now, option.as_deref()))
-                        .map(move |row| {
-                            let mut version = row.version;
+            Box::pin(stream::iter(
+                rows.into_iter()
+                    .filter(move |(_, row)| by(row, now, option.as_deref()))
+                    .take(limit)
+                    .filter_map(move |(_, row)| {
+                        let mut version = row.version;
 
-                            if let Some(mask) = options.mask() {
-                                version = version.mask(mask);
-                            }
+                        if let Some(mask) = options.mask() {
+                            version = version.mask(mask);
+                        }
 
-                            Ok(Saved::new(
-                                options
-                                    .transcoder()
-                                    .decode(&row.schema, &row.data)
-                                    .map_err(StoreError::InvalidEncoding)?,
-                                version,
-                            ))
-                        }),
-                ))
-            }
+                        options
+                            .decode(&row.schema, version, &row.data)
+                            .map_err(StoreError::InvalidEncoding)
+                            .map(|event| event.map(|event| Saved::new(event, version)))
+                            .transpose()
+                    }),
+            ))
         } else {
             let options = self.options.clone();
             let rows: Vec<_> = table.values().flatten().flatten().cloned().collect();
 
-            Box::pin(stream::iter(rows.into_iter().map(move |row| {
+            Box::pin(stream::iter(rows.into_iter().filter_map(move |row| {
                 let mut version = row.version;
 
                 if let Some(mask) = options.mask() {
                     version = version.mask(mask);
                 }
 
-                Ok(Saved::new(
-                    options
-                        .transcoder()
-                        .decode(&row.schema, &row.data)
-                        .map_err(StoreError::InvalidEncoding)?,
-                    version,
-                ))
+                options
+                    .decode(&row.schema, version, &row.data)
+                    .map_err(StoreError::InvalidEncoding)
+                    .map(|event| event.map(|event| Saved::new(event, version)))
+                    .transpose()
             })))
         }
     }
 
-    async fn save(
-        &self,
-        id: &T,
-        mut expected_version: Version,
-        events: &[Box<dyn Event>],
-    ) -> Result<Version, StoreError<T>> {
-        if events.is_empty() {
-            return Ok(expected_version);
+    async fn load_raw<'a>(&self, predicate: Option<&'a Predicate<'a, T>>) -> RawEventStream<'a, T> {
+        if self.order.is_none() && predicate.is_some_and(Predicate::paged) {
+            return Box::pin(once(ready(Err(StoreError::Unsupported))));
         }
 
-        if expected_version != Version::default()
-            && let Some(mask) = self.options.mask()
-        {
-            expected_version = expected_version.unmask(mask);
-        }
+        let table = self.table.read().unwrap();
+        let now = self.options.clock().now();
+        let mask = self.options.mask();
+        let selected: Vec<_> = if let Some(id) = predicate.and_then(|p| p.id) {
+            table.get_key_value(id).into_iter().collect()
+        } else {
+            table.iter().collect()
+        };
+        let mut rows = Vec::new();
 
-        if expected_version.invalid() {
-            return Err(StoreError::InvalidVersion);
+        for (id, batches) in selected {
+            let index = predicate
+                .and_then(|p| select_version(None, p, mask))
+                .unwrap_or_default();
+
+            rows.extend(
+                batches
+                    .iter()
+                    .skip(index)
+                    .flatten()
+                    .map(|row| (id.clone(), row.clone())),
+            );
         }
 
-        if events.len() > <Version as EncodedVersion>::max().sequence() as usize {
-            return Err(StoreError::BatchTooLarge(
-                <Version as EncodedVersion>::max().sequence(),
-            ));
+        if let Some(predicate) = predicate
+            && let Some(order) = self.order
+        {
+            rows = paginate(rows, predicate, mask, order);
         }
 
-        let mut table = self.table.write().unwrap();
-
-        if let Some(rows) = table.get(id) {
-            let count = rows.len();
+        let limit = predicate.and_then(|p| p.limit).unwrap_or(usize::MAX);
+        let events: Vec<_> = rows
+            .into_iter()
+            .filter(|(_, row)| by(row, now, predicate))
+            .take(limit)
+            .map(|(id, row)| {
+                let mut version = row.version;
 
-            if count > 0 && count > expected_version.number() as usize {
-                if self.options.concurrency().enforced() {
-                    return Err(StoreError::Conflict(id.clone(), expected_version.number()));
-                } else {
-                    expected_version = new_version(count as u32);
+                if let Some(mask) = mask {
+                    version = version.mask(mask);
                 }
-            }
-        } else if expected_version.number() > 1 {
-            return Err(StoreError::Deleted(id.clone()));
-        }
 
-        let mut version = expected_version.next_version();
-        let mut rows = Vec::new();
+                let descriptor = Descriptor::new(row.schema, version, row.data);
+                Ok(RawEvent::new(id, row.stored_on, descriptor))
+            })
+            .collect();
 
-        for event in events {
-            let row = Row {
-                schema: event.schema(),
-                version,
-                data: self
-                    .options
-                    .transcoder()
-                    .encode(event.as_ref())
-                    .map_err(StoreError::InvalidEncoding)?,
-            };
+        Box::pin(stream::iter(events))
+    }
 
-            rows.push(row);
-            version = version.next_sequence();
+    async fn head(&self, id: &T) -> Result<Head<T>, StoreError<T>> {
+        let table = self.table.read().unwrap();
+        let Some(rows) = table.get(id) else {
+            return Ok(Head::empty(id.clone()));
+        };
+        let (Some(first), Some(last)) = (
+            rows.first().and_then(|rows| rows.first()),
+            rows.last().and_then(|rows| rows.last()),
+        ) else {
+            return Ok(Head::tombstone(id.clone()));
+        };
+        let mut version = last.version;
+
+        if let Some(mask) = self.options.mask() {
+            version = version.mask(mask);
         }
 
-        version = rows.last().unwrap().version;
-        let mut rows = vec![rows];
+        Ok(Head::new(
+            id.clone(),
+            version,
+            rows.iter().map(Vec::len).sum::<usize>() as u64,
+            first.stored_on,
+            last.stored_on,
+        ))
+    }
 
-        table
-            .entry(id.clone())
-            .and_modify(|row| row.append(&mut rows))
-            .or_insert(rows);
+    async fn save(
+        &self,
+        id: &T,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<T>> {
+        self.append(id, expected_version, events, None)
+    }
 
-        if let Some(mask) = self.options.mask() {
-            version = version.mask(mask);
+    async fn save_once(
+        &self,
+        id: &T,
+        expected_version: Version,
+        events: &[Box<dyn Event>],
+        message_id: &str,
+    ) -> Result<Version, StoreError<T>> {
+        self.append(id, expected_version, events, Some(message_id))
+    }
+
+    async fn save_expecting(
+        &self,
+        id: &T,
+        expected: ExpectedVersion,
+        events: &[Box<dyn Event>],
+    ) -> Result<Version, StoreError<T>> {
+        let mut table = self.table.write().unwrap();
+        let current = table
+            .get(id)
+            .and_then(|rows| rows.last()?.last())
+            .map(|row| {
+                let mut version = row.version;
+
+                if let Some(mask) = self.options.mask() {
+                    version = version.mask(mask);
+                }
+
+                version
+            });
+        let expected_version = match expected {
+            ExpectedVersion::Any => current.unwrap_or_default(),
+            ExpectedVersion::NoStream => Version::default(),
+            ExpectedVersion::StreamExists => {
+                current.ok_or_else(|| StoreError::NotFound(id.clone()))?
+            }
+            ExpectedVersion::Exact(version) => match current {
+                Some(current) if current == version => version,
+                Some(_) => {
+                    let version = if let Some(mask) = self.options.mask() {
+                        version.unmask(mask)
+                    } else {
+                        version
+                    };
+
+                    return Err(StoreError::Conflict(id.clone(), version.number()));
+                }
+                None => return Err(StoreError::NotFound(id.clone())),
+            },
+        };
+
+        self.write(
+            &mut table,
+            id,
+            expected_version,
+            events,
+            None,
+            expected.concurrency(),
+        )
+    }
+
+    async fn save_all(&self, appends: &[Append<'_, T>]) -> Result<Vec<Version>, StoreError<T>> {
+        let mut table = self.table.write().unwrap();
+        let mut versions = Vec::with_capacity(appends.len());
+        let mut undo = Vec::with_capacity(appends.len());
+
+        for append in appends {
+            let id = append.id();
+            let count = table.get(id).map(Vec::len);
+
+            match self.write(
+                &mut table,
+                id,
+                append.expected_version(),
+                append.events(),
+                None,
+                self.options.concurrency(),
+            ) {
+                Ok(version) => {
+                    versions.push(version);
+                    undo.push((id, count));
+                }
+                Err(error) => {
+                    // restore the table to its state before any changes were written
+                    for (id, count) in undo.into_iter().rev() {
+                        if let Some(count) = count {
+                            if let Some(rows) = table.get_mut(id) {
+                                rows.truncate(count);
+                            }
+                        } else {
+                            let _ = table.remove(id);
+                        }
+                    }
+
+                    return Err(error);
+                }
+            }
         }
 
-        Ok(version)
+        Ok(versions)
     }
 
     async fn delete(&self, id: &T) -> Result<(), StoreError<T>> {
@@ -529,7 +857,10 @@ impl<T: Clone + Debug + Eq + Hash + Send + Sync + 'static> event::Store<T> for E
             snapshots.prune(id, None).await?;
         }
 
-        let _ = self.table.write().unwrap().remove(id);
+        if let Some(rows) = self.table.write().unwrap().get_mut(id) {
+            rows.clear();
+        }
+
         Ok(())
     }
 }
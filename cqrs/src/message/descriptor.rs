@@ -27,3 +27,63 @@ impl Descriptor {
         }
     }
 }
+
+cfg_if::cfg_if! {
+    if #[cfg(any(feature = "json", feature = "cbor", feature = "message-pack"))] {
+        use super::EncodingError;
+        use std::{borrow::Cow, error::Error};
+
+        #[inline]
+        fn failed<E: Error + Send + 'static>(error: E) -> EncodingError {
+            EncodingError::Failed(Box::new(error))
+        }
+
+        impl Descriptor {
+            fn decompressed(&self) -> Result<Cow<'_, [u8]>, EncodingError> {
+                cfg_if::cfg_if! {
+                    if #[cfg(any(feature = "gzip", feature = "lz4", feature = "zstd"))] {
+                        crate::encoding::decompress(&self.content).map_err(failed)
+                    } else {
+                        Ok(Cow::Borrowed(&self.content))
+                    }
+                }
+            }
+
+            /// Decodes the content as a generic JSON [value](serde_json::Value).
+            ///
+            /// # Remarks
+            ///
+            /// The message type does not need to be known or registered, which is useful for tooling
+            /// that browses stored messages. Compressed content is decompressed first when the
+            /// corresponding compression feature is enabled.
+            #[cfg(feature = "json")]
+            pub fn to_json(&self) -> Result<serde_json::Value, EncodingError> {
+                serde_json::from_slice(&self.decompressed()?).map_err(failed)
+            }
+
+            /// Decodes the content as a generic CBOR [value](ciborium::Value).
+            ///
+            /// # Remarks
+            ///
+            /// The message type does not need to be known or registered, which is useful for tooling
+            /// that browses stored messages. Compressed content is decompressed first when the
+            /// corresponding compression feature is enabled.
+            #[cfg(feature = "cbor")]
+            pub fn to_cbor(&self) -> Result<ciborium::Value, EncodingError> {
+                ciborium::from_reader(&*self.decompressed()?).map_err(failed)
+            }
+
+            /// Decodes the content as a generic Message Pack [value](rmpv::Value).
+            ///
+            /// # Remarks
+            ///
+            /// The message type does not need to be known or registered, which is useful for tooling
+            /// that browses stored messages. Compressed content is decompressed first when the
+            /// corresponding compression feature is enabled.
+            #[cfg(feature = "message-pack")]
+            pub fn to_message_pack(&self) -> Result<rmpv::Value, EncodingError> {
+                rmpv::decode::read_value(&mut &*self.decompressed()?).map_err(failed)
+            }
+        }
+    }
+}
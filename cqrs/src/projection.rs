@@ -1,5 +1,11 @@
+mod error;
 mod filter;
 mod projector;
+mod runner;
+mod sink;
 
+pub use error::ProjectionError;
 pub use filter::{Filter, FilterBuilder};
-pub use projector::Projector;
\ No newline at end of file
+pub use projector::Projector;
+pub use runner::{ProgressStream, ProjectionStream, Runner};
+pub use sink::Sink;
@@ -0,0 +1,31 @@
+use crate::event::StoreError;
+use std::{error::Error, fmt::Debug};
+use thiserror::Error;
+
+/// Represents the possible projection errors.
+#[derive(Error, Debug)]
+pub enum ProjectionError<I: Debug + Send> {
+    /// Indicates the identifiers to project could not be read from the
+    /// [store](crate::event::Store).
+    #[error(transparent)]
+    Store(#[from] StoreError<I>),
+
+    /// Indicates the projection of the specified identifier failed.
+    #[error("the projection for identifier {0:?} failed: {1}")]
+    Projector(I, Box<dyn Error + Send>),
+
+    /// Indicates the projection of the specified identifier could not be written to its
+    /// [sink](super::Sink).
+    #[error("the projection for identifier {0:?} could not be written: {1}")]
+    Sink(I, Box<dyn Error + Send>),
+}
+
+impl<I: Debug + Send> ProjectionError<I> {
+    /// Gets the identifier associated with the error, if any.
+    pub fn id(&self) -> Option<&I> {
+        match self {
+            Self::Projector(id, _) | Self::Sink(id, _) => Some(id),
+            Self::Store(_) => None,
+        }
+    }
+}
@@ -1,14 +1,19 @@
 use crate::event::PredicateBuilder;
-use crate::Range;
+use crate::message::Schema;
+use crate::{Range, Version};
 use std::fmt::Debug;
+use std::ops::{Bound, Bound::Unbounded};
 use std::time::SystemTime;
 use uuid::Uuid;
 
 /// Represents the filter applied to projections.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Filter<'a, T: Debug + Send = Uuid> {
     id: Option<&'a T>,
     stored_on: Range<SystemTime>,
+    types: Vec<Schema>,
+    version: Bound<Version>,
+    correlation_id: Option<&'a str>,
 }
 
 impl<'a, T: Debug + Send> Filter<'a, T> {
@@ -21,6 +26,9 @@ impl<'a, T: Debug + Send> Filter<'a, T> {
         Self {
             id,
             stored_on: Range::all(),
+            types: Vec::new(),
+            version: Unbounded,
+            correlation_id: None,
         }
     }
 
@@ -33,6 +41,31 @@ impl<'a, T: Debug + Send> Filter<'a, T> {
     pub fn stored_on(&self) -> &Range<SystemTime> {
         &self.stored_on
     }
+
+    /// Gets the event types to filter by.
+    ///
+    /// # Remarks
+    ///
+    /// When no types are specified, a projector only reads the event types it receives.
+    pub fn types(&self) -> &[Schema] {
+        &self.types
+    }
+
+    /// Gets the event [version](Version) to filter by.
+    pub fn version(&self) -> Bound<Version> {
+        self.version
+    }
+
+    /// Gets the correlation identifier of events to filter by, if any.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id
+    }
+}
+
+impl<T: Debug + Send> Default for Filter<'_, T> {
+    fn default() -> Self {
+        Self::new(None)
+    }
 }
 
 /// Represents a builder to create a [Filter].
@@ -59,6 +92,40 @@ impl<'a, T: Debug + Send> FilterBuilder<'a, T> {
         self
     }
 
+    /// Adds an event type to filter by.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the event type [schema](Schema)
+    ///
+    /// # Remarks
+    ///
+    /// Use [Schema::versionless] to match any version of a [schema](Schema).
+    pub fn add_type(mut self, value: Schema) -> Self {
+        self.0.types.push(value);
+        self
+    }
+
+    /// Sets the event [version](Version) to filter by.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the event [version](Version)
+    pub fn version(mut self, value: Bound<Version>) -> Self {
+        self.0.version = value;
+        self
+    }
+
+    /// Sets the correlation identifier of events to filter by.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the correlation identifier
+    pub fn correlation_id(mut self, value: &'a str) -> Self {
+        self.0.correlation_id = Some(value);
+        self
+    }
+
     /// Builds and returns a new [Filter].
     pub fn build(self) -> Filter<'a, T> {
         self.0
@@ -73,6 +140,18 @@ impl<'a, T: Debug + Send> From<FilterBuilder<'a, T>> for Filter<'a, T> {
 
 impl<'a, T: Debug + Send> From<&Filter<'a, T>> for PredicateBuilder<'a, T> {
     fn from(value: &Filter<'a, T>) -> Self {
-        Self::new(value.id).stored_on(value.stored_on().clone())
+        let mut builder = Self::new(value.id)
+            .stored_on(value.stored_on().clone())
+            .version(value.version);
+
+        for schema in &value.types {
+            builder = builder.add_type(schema.clone());
+        }
+
+        if let Some(cid) = value.correlation_id {
+            builder = builder.correlation_id(cid);
+        }
+
+        builder
     }
 }
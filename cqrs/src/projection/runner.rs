@@ -0,0 +1,131 @@
+use super::{FilterBuilder, ProjectionError, Projector, Sink};
+use crate::{Range, event::Store, event::StoreError};
+use futures::{Stream, StreamExt};
+use std::{fmt::Debug, marker::PhantomData, num::NonZeroUsize, pin::Pin, sync::Arc};
+use std::{thread::available_parallelism, time::SystemTime};
+use uuid::Uuid;
+
+/// Represents a [stream](Stream) of projections created by a [Runner].
+pub type ProjectionStream<'a, I, P> =
+    Pin<Box<dyn Stream<Item = Result<(I, P), ProjectionError<I>>> + Send + 'a>>;
+
+/// Represents a [stream](Stream) of identifiers whose projections have been written to a
+/// [sink](Sink) by a [Runner].
+pub type ProgressStream<'a, I> =
+    Pin<Box<dyn Stream<Item = Result<I, ProjectionError<I>>> + Send + 'a>>;
+
+/// Represents a runner that projects many identifiers concurrently.
+///
+/// # Remarks
+///
+/// A runner enumerates the identifiers in a [store](Store) and creates a new [projector](Projector)
+/// for each identifier using the provided factory function. At most the configured number of
+/// projections run at the same time. The outcome of each projection is yielded in the order in
+/// which it completes.
+pub struct Runner<F, I = Uuid, P = ()>
+where
+    I: Debug + Send,
+{
+    store: Arc<dyn Store<I>>,
+    factory: F,
+    parallelism: usize,
+    stored_on: Range<SystemTime>,
+    _projection: PhantomData<fn() -> P>,
+}
+
+impl<F, R, I, P> Runner<F, I, P>
+where
+    F: Fn(&I) -> R + Send + Sync,
+    R: Projector<I, P> + Send,
+    I: Clone + Debug + Send + Sync + 'static,
+    P: Default + Send + 'static,
+{
+    /// Initializes a new [Runner].
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - the [store](Store) used to enumerate identifiers
+    /// * `factory` - the function used to create a [projector](Projector) for an identifier
+    ///
+    /// # Remarks
+    ///
+    /// The default parallelism is the amount of parallelism available to the current process.
+    pub fn new(store: Arc<dyn Store<I>>, factory: F) -> Self {
+        Self {
+            store,
+            factory,
+            parallelism: available_parallelism().map_or(1, NonZeroUsize::get),
+            stored_on: Range::all(),
+            _projection: PhantomData,
+        }
+    }
+
+    /// Sets the maximum number of projections that run concurrently.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the maximum number of concurrent projections
+    ///
+    /// # Remarks
+    ///
+    /// A value of zero is treated as one.
+    pub fn parallelism(mut self, value: usize) -> Self {
+        self.parallelism = value.max(1);
+        self
+    }
+
+    /// Sets the [date](SystemTime) [range](Range) used to select the identifiers to project.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the [date](SystemTime) [range](Range)
+    pub fn stored_on<V: Into<Range<SystemTime>>>(mut self, value: V) -> Self {
+        self.stored_on = value.into();
+        self
+    }
+
+    /// Runs the projectors and streams the projection created for each identifier.
+    pub async fn run(&self) -> ProjectionStream<'_, I, P> {
+        let ids = self.store.ids(self.stored_on.clone()).await;
+        Box::pin(
+            ids.map(|id| self.project(id))
+                .buffer_unordered(self.parallelism),
+        )
+    }
+
+    /// Runs the projectors, writes each projection to the provided [sink](Sink), and streams the
+    /// identifier of each projection written.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - the [sink](Sink) projections are written to
+    pub async fn run_into<'a, S>(&'a self, sink: &'a S) -> ProgressStream<'a, I>
+    where
+        S: Sink<I, P>,
+    {
+        let ids = self.store.ids(self.stored_on.clone()).await;
+
+        Box::pin(
+            ids.map(move |id| async move {
+                let (id, projection) = self.project(id).await?;
+
+                match sink.write(&id, projection).await {
+                    Ok(()) => Ok(id),
+                    Err(error) => Err(ProjectionError::Sink(id, error)),
+                }
+            })
+            .buffer_unordered(self.parallelism),
+        )
+    }
+
+    async fn project(&self, id: Result<I, StoreError<I>>) -> Result<(I, P), ProjectionError<I>> {
+        let id = id?;
+        let mut projector = (self.factory)(&id);
+        let filter = FilterBuilder::new(Some(&id)).build();
+
+        match projector.run(Some(&filter)).await {
+            Ok(projection) => Ok((id, projection)),
+            Err(error) => Err(ProjectionError::Projector(id, error)),
+        }
+    }
+}
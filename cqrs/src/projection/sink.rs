@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use std::error::Error;
+use uuid::Uuid;
+
+/// Defines the behavior of a sink that projections are written to.
+#[async_trait]
+pub trait Sink<I = Uuid, P = ()>: Send + Sync
+where
+    I: Send + Sync,
+    P: Send,
+{
+    /// Writes a projection to the sink.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier the projection was created for
+    /// * `projection` - the projection to write
+    ///
+    /// # Remarks
+    ///
+    /// A sink may be written to concurrently by a [runner](super::Runner).
+    async fn write(&self, id: &I, projection: P) -> Result<(), Box<dyn Error + Send>>;
+}
@@ -1,6 +1,6 @@
 use crate::{
     Aggregate,
-    event::{Predicate, PredicateBuilder, Store, StoreError},
+    event::{ExpectedVersion, Predicate, PredicateBuilder, Store, StoreError},
     message::EncodingError,
 };
 use cfg_if::cfg_if;
@@ -49,6 +49,7 @@ impl<T: Debug + Send + 'static> From<StoreError<T>> for RepositoryError<T> {
         match value {
             StoreError::Conflict(id, version) => Self::Conflict(id, version),
             StoreError::Deleted(id) => Self::NotFound(id),
+            StoreError::NotFound(id) => Self::NotFound(id),
             StoreError::InvalidEncoding(error) => Self::InvalidEncoding(error),
             StoreError::InvalidVersion => Self::InvalidVersion,
             StoreError::Unsupported => Self::Unsupported,
@@ -152,6 +153,77 @@ where
         Ok(())
     }
 
+    /// Saves the specified [aggregate](Aggregate) with an explicit expectation of its stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `aggregate` - the [aggregate](Aggregate) to save
+    /// * `expected` - the [expected version](ExpectedVersion) of the aggregate stream
+    ///
+    /// # Remarks
+    ///
+    /// This is most useful when creating an aggregate with [ExpectedVersion::NoStream], which fails
+    /// with [RepositoryError::Conflict] if the aggregate identifier has already been used.
+    pub async fn save_expecting(
+        &self,
+        aggregate: &mut A,
+        expected: ExpectedVersion,
+    ) -> Result<(), RepositoryError<A::ID>> {
+        let id = aggregate.id().clone();
+        let mut changes = aggregate.changes();
+
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let version = self
+            .store
+            .save_expecting(&id, expected, changes.uncommitted())
+            .await?;
+
+        changes.accept(version);
+        Ok(())
+    }
+
+    /// Saves the specified [aggregate](Aggregate) at most once for a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `aggregate` - the [aggregate](Aggregate) to save
+    /// * `message_id` - the identifier of the message, such as a command, that changed the aggregate
+    ///
+    /// # Remarks
+    ///
+    /// If the changes for the message have already been saved, the changes are accepted using the
+    /// previously assigned [version](crate::Version) and nothing new is appended. If the underlying
+    /// [store](Store) does not support idempotent saves, it will bubble up as
+    /// [RepositoryError::Unsupported].
+    pub async fn save_once(
+        &self,
+        aggregate: &mut A,
+        message_id: &str,
+    ) -> Result<(), RepositoryError<A::ID>> {
+        let id = aggregate.id().clone();
+        let mut changes = aggregate.changes();
+
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let version = self
+            .store
+            .save_once(
+                &id,
+                changes.expected_version(),
+                changes.uncommitted(),
+                message_id,
+            )
+            .await?;
+
+        changes.accept(version);
+        Ok(())
+    }
+
     /// Deletes the [aggregate](Aggregate) with the specified identifier.
     ///
     /// # Arguments
@@ -0,0 +1,97 @@
+use crate::{
+    Aggregate, ChangeSet, RepositoryError,
+    event::{Append, Store},
+};
+use std::{fmt::Debug, sync::Arc};
+use uuid::Uuid;
+
+/// Represents a unit of work that saves the changes of multiple [aggregates](Aggregate) atomically.
+///
+/// # Remarks
+///
+/// All of the registered [aggregates](Aggregate) must share the same [store](Store). The changes
+/// are either all saved or none of them are.
+pub struct UnitOfWork<'a, ID: Debug + Send = Uuid> {
+    store: Arc<dyn Store<ID>>,
+    changes: Vec<(ID, ChangeSet<'a>)>,
+}
+
+impl<'a, ID> UnitOfWork<'a, ID>
+where
+    ID: Clone + Debug + Send + Sync + 'static,
+{
+    /// Initializes a new [UnitOfWork].
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - the underlying [store](Store)
+    pub fn new<S: Store<ID> + 'static>(store: S) -> Self {
+        Self {
+            store: Arc::new(store),
+            changes: Vec::new(),
+        }
+    }
+
+    /// Registers the specified [aggregate](Aggregate) with the unit of work.
+    ///
+    /// # Arguments
+    ///
+    /// * `aggregate` - the [aggregate](Aggregate) whose changes should be saved
+    pub fn register<A: Aggregate<ID = ID>>(&mut self, aggregate: &'a mut A) {
+        let id = aggregate.id().clone();
+        self.changes.push((id, aggregate.changes()));
+    }
+
+    /// Gets a value indicating whether there are any changes to save.
+    pub fn is_empty(&self) -> bool {
+        self.changes.iter().all(|(_, changes)| changes.is_empty())
+    }
+
+    /// Saves the changes of all registered [aggregates](Aggregate) as a single, atomic operation.
+    ///
+    /// # Remarks
+    ///
+    /// If the underlying [store](Store) does not support atomic saves across identifiers, it will
+    /// bubble up as [RepositoryError::Unsupported].
+    pub async fn commit(self) -> Result<(), RepositoryError<ID>> {
+        let mut changes: Vec<_> = self
+            .changes
+            .into_iter()
+            .filter(|(_, changes)| !changes.is_empty())
+            .collect();
+
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let versions = {
+            let appends: Vec<_> = changes
+                .iter_mut()
+                .map(|(id, changes)| {
+                    let expected_version = changes.expected_version();
+                    Append::new(id.clone(), expected_version, &*changes.uncommitted())
+                })
+                .collect();
+
+            self.store.save_all(&appends).await?
+        };
+
+        for ((_, changes), version) in changes.iter_mut().zip(versions) {
+            changes.accept(version);
+        }
+
+        Ok(())
+    }
+}
+
+impl<ID> From<Arc<dyn Store<ID>>> for UnitOfWork<'_, ID>
+where
+    ID: Debug + Send,
+{
+    fn from(value: Arc<dyn Store<ID>>) -> Self {
+        Self {
+            store: value,
+            changes: Vec::new(),
+        }
+    }
+}
@@ -9,7 +9,14 @@ use std::{array::TryFromSliceError, fmt::{self, Debug, Formatter, Result as Form
 /// A version is opaque to consumers. The internal representation should be considered encoded binary.
 #[repr(transparent)]
 #[cfg_attr(
-    any(feature = "cbor", feature = "json", feature = "message-pack"),
+    any(
+        feature = "avro",
+        feature = "bincode",
+        feature = "cbor",
+        feature = "json",
+        feature = "message-pack",
+        feature = "postcard"
+    ),
     derive(serde::Deserialize, serde::Serialize),
     serde(transparent)
 )]
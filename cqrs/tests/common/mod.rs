@@ -15,4 +15,4 @@ impl<T, E: Error + Send + 'static> BoxErr<T> for Result<T, E> {
     fn box_err(self) -> Result<T, Box<dyn Error + Send>> {
         self.map_err(|e| Box::new(e) as Box<dyn Error + Send>)
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,10 @@
 use super::domain::{Credited, Debited, Statement};
 use async_trait::async_trait;
 use cqrs::{
+    Clock, Range, Version, WallClock,
     event::{Event, EventStream, IdStream, Predicate, Receiver, Store, StoreError},
     projection::{FilterBuilder, Projector},
-    projectors, Clock, Range, Version, WallClock,
+    projectors,
 };
 use std::{error::Error, sync::Arc, time::SystemTime};
 use std::{fmt::Debug, marker::PhantomData};
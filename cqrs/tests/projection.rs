@@ -1,12 +1,49 @@
 mod common;
 
+use async_trait::async_trait;
 use common::{
-    TestResult,
-    domain::{self, Account},
+    BoxErr, TestResult,
+    domain::{self, Account, Credited, Statement},
     projector::StatementGenerator,
 };
 use cqrs::{Repository, RepositoryError, VirtualClock, event::{Store, StoreOptions}, in_memory::EventStore};
-use std::sync::Arc;
+use cqrs::{
+    message::Encoded,
+    projection::{FilterBuilder, Projector, Runner, Sink},
+};
+use futures::TryStreamExt;
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Arc, Mutex},
+};
+
+#[derive(Default)]
+struct Statements(Mutex<HashMap<String, f32>>);
+
+#[async_trait]
+impl Sink<String, Statement> for Statements {
+    async fn write(&self, id: &String, projection: Statement) -> Result<(), Box<dyn Error + Send>> {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(id.clone(), projection.balance);
+        Ok(())
+    }
+}
+
+async fn open_accounts(store: &Arc<dyn Store<String>>) -> TestResult {
+    let repository: Repository<Account> = store.clone().into();
+
+    for (id, amount) in [("1", 10.0), ("2", 20.0), ("3", 30.0)] {
+        let mut account = Account::open(id);
+        account.credit(amount);
+        account.debit(5.0);
+        repository.save(&mut account).await.box_err()?;
+    }
+
+    Ok(())
+}
 
 #[tokio::test]
 async fn projector_should_produce_monthly_statement() -> TestResult<RepositoryError<String>> {
@@ -35,3 +72,93 @@ async fn projector_should_produce_monthly_statement() -> TestResult<RepositoryEr
     assert_eq!(statement.balance, 75.0);
     Ok(())
 }
+
+#[tokio::test]
+async fn projector_should_only_receive_filtered_event_types() -> TestResult<RepositoryError<String>>
+{
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(domain::transcoder::events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let id = String::from("42");
+    let mut account = Account::open(&id);
+
+    account.credit(25.0);
+    account.credit(50.0);
+    account.debit(25.0);
+    repository.save(&mut account).await?;
+
+    let mut projector = StatementGenerator::new(store.clone());
+    let filter = FilterBuilder::new(Some(&id))
+        .add_type(Credited::schema())
+        .build();
+
+    // act
+    let statement =
+        <StatementGenerator as Projector<String, Statement>>::run(&mut projector, Some(&filter))
+            .await?;
+
+    // assert
+    assert_eq!(statement.balance, 75.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn runner_should_project_all_identifiers() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(domain::transcoder::events())
+        .build();
+    let store: Arc<dyn Store<String>> = Arc::new(EventStore::<String>::new(options));
+
+    open_accounts(&store).await?;
+
+    let runner = Runner::new(store.clone(), |_: &String| {
+        StatementGenerator::new(store.clone())
+    })
+    .parallelism(2);
+
+    // act
+    let mut statements: Vec<(String, Statement)> =
+        runner.run().await.try_collect().await.box_err()?;
+
+    // assert
+    statements.sort_by(|l, r| l.0.cmp(&r.0));
+    let balances: Vec<_> = statements
+        .iter()
+        .map(|(id, s)| (id.as_str(), s.balance))
+        .collect();
+    assert_eq!(balances, [("1", 5.0), ("2", 15.0), ("3", 25.0)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn runner_should_write_projections_to_sink() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(domain::transcoder::events())
+        .build();
+    let store: Arc<dyn Store<String>> = Arc::new(EventStore::<String>::new(options));
+    let sink = Statements::default();
+
+    open_accounts(&store).await?;
+
+    let runner = Runner::new(store.clone(), |_: &String| {
+        StatementGenerator::new(store.clone())
+    })
+    .parallelism(2);
+
+    // act
+    let mut ids: Vec<String> = runner.run_into(&sink).await.try_collect().await.box_err()?;
+
+    // assert
+    ids.sort();
+    assert_eq!(ids, ["1", "2", "3"]);
+    assert_eq!(sink.0.lock().unwrap()["2"], 15.0);
+    Ok(())
+}
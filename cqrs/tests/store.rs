@@ -1,15 +1,20 @@
 mod common;
 
 use common::{
-    TestResult,
-    domain::{Account, transcoder::events},
+    BoxErr, TestResult,
+    domain::{Account, Credited, transcoder::events},
 };
 use cqrs::{
-    Repository, RepositoryError, VirtualClock,
-    event::{Store, StoreOptions},
+    Aggregate, Repository, RepositoryError, UnitOfWork, Version, VirtualClock,
+    event::{
+        self, ContinuationToken, DecodePolicy, Direction, ExpectedVersion, PredicateBuilder, Store,
+        StoreOptions, UnknownEvent,
+    },
     in_memory::EventStore,
+    message::{EncodingError, Encoded},
     prelude::*,
 };
+use futures::TryStreamExt;
 use std::sync::Arc;
 
 #[tokio::test]
@@ -90,3 +95,678 @@ async fn repository_should_get_aggregate_by_id_using_di() -> TestResult<Reposito
     assert_eq!(account.balance, 75.0);
     Ok(())
 }
+
+#[tokio::test]
+async fn repository_should_save_aggregate_once_per_message() -> TestResult<RepositoryError<String>>
+{
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account = Account::open("42");
+    let mut retry = Account::open("42");
+
+    account.credit(25.0);
+    account.credit(50.0);
+    retry.credit(25.0);
+    retry.credit(50.0);
+    repository.save_once(&mut account, "deposit-1").await?;
+
+    // act
+    repository.save_once(&mut retry, "deposit-1").await?;
+
+    // assert
+    assert_eq!(store.size(), 2);
+    assert_eq!(account.version(), retry.version());
+    Ok(())
+}
+
+#[tokio::test]
+async fn repository_should_not_save_new_aggregate_when_stream_exists() {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account = Account::open("42");
+    let mut duplicate = Account::open("42");
+
+    account.credit(25.0);
+    duplicate.credit(50.0);
+    repository.save(&mut account).await.unwrap();
+
+    // act
+    let result = repository
+        .save_expecting(&mut duplicate, ExpectedVersion::NoStream)
+        .await;
+
+    // assert
+    assert!(matches!(result, Err(RepositoryError::Conflict(_, _))));
+    assert_eq!(store.size(), 1);
+}
+
+#[tokio::test]
+async fn repository_should_not_save_aggregate_when_stream_does_not_exist() {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account = Account::open("42");
+
+    account.credit(25.0);
+
+    // act
+    let result = repository
+        .save_expecting(&mut account, ExpectedVersion::StreamExists)
+        .await;
+
+    // assert
+    assert_eq!(result, Err(RepositoryError::NotFound("42".into())));
+    assert_eq!(store.size(), 0);
+}
+
+#[tokio::test]
+async fn repository_should_not_save_aggregate_when_exact_version_is_not_at_stream() {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account = Account::open("42");
+    let mut other = Account::open("7");
+    let mut ahead = Account::open("42");
+    let mut missing = Account::open("13");
+
+    account.credit(25.0);
+    other.credit(25.0);
+    repository.save(&mut account).await.unwrap();
+    repository.save(&mut other).await.unwrap();
+    other.credit(50.0);
+    repository.save(&mut other).await.unwrap();
+    ahead.credit(75.0);
+    missing.credit(10.0);
+
+    // act
+    let conflict = repository
+        .save_expecting(&mut ahead, ExpectedVersion::Exact(other.version()))
+        .await;
+    let not_found = repository
+        .save_expecting(&mut missing, ExpectedVersion::Exact(account.version()))
+        .await;
+
+    // assert
+    assert!(matches!(conflict, Err(RepositoryError::Conflict(_, _))));
+    assert_eq!(not_found, Err(RepositoryError::NotFound("13".into())));
+    assert_eq!(store.size(), 3);
+}
+
+#[tokio::test]
+async fn repository_should_save_aggregate_at_exact_version_after_multiple_events()
+-> TestResult<RepositoryError<String>> {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account = Account::open("42");
+
+    account.credit(25.0);
+    repository.save(&mut account).await?;
+
+    let version = account.version();
+
+    account.credit(50.0);
+
+    // act
+    repository
+        .save_expecting(&mut account, ExpectedVersion::Exact(version))
+        .await?;
+
+    // assert
+    let saved = repository.get(&"42".into(), None).await?;
+
+    assert_ne!(account.version(), version);
+    assert_eq!(saved.version(), account.version());
+    Ok(())
+}
+
+#[tokio::test]
+async fn repository_should_save_aggregate_with_any_version() -> TestResult<RepositoryError<String>>
+{
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account = Account::open("42");
+    let mut other = Account::open("42");
+
+    account.credit(25.0);
+    other.credit(50.0);
+    repository.save(&mut account).await?;
+
+    // act
+    repository
+        .save_expecting(&mut other, ExpectedVersion::Any)
+        .await?;
+
+    // assert
+    assert_eq!(store.size(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn head_should_describe_stream_without_loading_events() -> TestResult<RepositoryError<String>>
+{
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account = Account::open("42");
+
+    account.credit(25.0);
+    account.credit(50.0);
+    repository.save(&mut account).await?;
+    account.debit(10.0);
+    repository.save(&mut account).await?;
+
+    // act
+    let head = store.head(&"42".into()).await?;
+
+    // assert
+    assert!(head.exists());
+    assert!(!head.deleted());
+    assert_eq!(head.version(), account.version());
+    assert_eq!(head.count(), 3);
+    assert!(head.first_stored_on() <= head.last_stored_on());
+    Ok(())
+}
+
+#[tokio::test]
+async fn head_should_be_empty_when_stream_does_not_exist() -> TestResult<RepositoryError<String>> {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = EventStore::<String>::new(options);
+
+    // act
+    let head = store.head(&"42".into()).await?;
+
+    // assert
+    assert_eq!(head, event::Head::empty("42".into()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn head_should_indicate_deleted_stream() -> TestResult<RepositoryError<String>> {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .with_deletes()
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account = Account::open("42");
+
+    account.credit(25.0);
+    repository.save(&mut account).await?;
+    repository.delete(account.id()).await?;
+
+    // act
+    let head = store.head(&"42".into()).await?;
+
+    // assert
+    assert!(!head.exists());
+    assert!(head.deleted());
+    Ok(())
+}
+
+#[tokio::test]
+async fn unit_of_work_should_save_aggregates_atomically() -> TestResult<RepositoryError<String>> {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let mut from = Account::open("1");
+    let mut to = Account::open("2");
+
+    from.debit(25.0);
+    to.credit(25.0);
+    to.credit(10.0);
+
+    let mut work: UnitOfWork<String> = (store.clone() as Arc<dyn Store<String>>).into();
+
+    work.register(&mut from);
+    work.register(&mut to);
+
+    // act
+    work.commit().await?;
+
+    // assert
+    assert_eq!(store.size(), 3);
+    assert!(from.changes().is_empty());
+    assert!(to.changes().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn unit_of_work_should_not_save_any_aggregate_on_conflict()
+-> TestResult<RepositoryError<String>> {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .enforce_concurrency()
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut other = Account::open("2");
+    let mut from = Account::open("1");
+    let mut to = Account::open("2");
+
+    other.credit(50.0);
+    repository.save(&mut other).await?;
+    from.debit(25.0);
+    to.credit(25.0);
+
+    let mut work: UnitOfWork<String> = (store.clone() as Arc<dyn Store<String>>).into();
+
+    work.register(&mut from);
+    work.register(&mut to);
+
+    // act
+    let result = work.commit().await;
+
+    // assert
+    assert_eq!(result, Err(RepositoryError::Conflict("2".into(), 0)));
+    assert_eq!(store.size(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_raw_should_return_undecoded_events() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let id = String::from("42");
+    let mut account = Account::open(id.clone());
+
+    account.credit(25.0);
+    account.debit(10.0);
+    repository.save(&mut account).await.box_err()?;
+
+    let predicate = PredicateBuilder::new(Some(&id)).build();
+
+    // act
+    let events: Vec<_> = store
+        .load_raw(Some(&predicate))
+        .await
+        .try_collect()
+        .await
+        .box_err()?;
+
+    // assert
+    let amounts: Vec<_> = events
+        .iter()
+        .map(|event| event.descriptor().to_json().unwrap()["amount"].clone())
+        .collect();
+
+    assert!(events.iter().all(|event| event.id() == &id));
+    assert_eq!(amounts, vec![25.0, 10.0]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_should_read_last_events_backward() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::ordered(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let id = String::from("42");
+    let mut account = Account::open(id.clone());
+
+    account.credit(10.0);
+    account.credit(20.0);
+    repository.save(&mut account).await.box_err()?;
+    account.credit(30.0);
+    repository.save(&mut account).await.box_err()?;
+
+    let predicate = PredicateBuilder::new(Some(&id))
+        .direction(Direction::Backward)
+        .limit(2)
+        .build();
+
+    // act
+    let events: Vec<_> = store
+        .load_raw(Some(&predicate))
+        .await
+        .try_collect()
+        .await
+        .box_err()?;
+
+    // assert
+    let amounts: Vec<_> = events
+        .iter()
+        .map(|event| event.descriptor().to_json().unwrap()["amount"].clone())
+        .collect();
+
+    assert_eq!(amounts, vec![30.0, 20.0]);
+    assert_eq!(events[0].descriptor().version, account.version());
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_should_continue_after_token() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::ordered(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let id = String::from("42");
+    let mut account = Account::open(id.clone());
+
+    account.credit(10.0);
+    account.credit(20.0);
+    repository.save(&mut account).await.box_err()?;
+    account.credit(30.0);
+    repository.save(&mut account).await.box_err()?;
+
+    let predicate = PredicateBuilder::new(Some(&id)).limit(2).build();
+    let page: Vec<_> = store
+        .load(Some(&predicate))
+        .await
+        .try_collect()
+        .await
+        .box_err()?;
+    let token = ContinuationToken::new(id.clone(), page.last().unwrap().version());
+    let predicate = PredicateBuilder::new(Some(&id))
+        .limit(2)
+        .after(&token)
+        .build();
+
+    // act
+    let next: Vec<_> = store
+        .load(Some(&predicate))
+        .await
+        .try_collect()
+        .await
+        .box_err()?;
+
+    // assert
+    assert_eq!(page.len(), 2);
+    assert_eq!(next.len(), 1);
+    assert_eq!(next[0].version(), account.version());
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_raw_should_page_across_identifiers() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::ordered(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let mut account1 = Account::open("1");
+    let mut account2 = Account::open("2");
+
+    account1.credit(10.0);
+    account1.credit(20.0);
+    account2.credit(30.0);
+    account2.credit(40.0);
+    repository.save(&mut account1).await.box_err()?;
+    repository.save(&mut account2).await.box_err()?;
+
+    let mut token = None;
+    let mut amounts = Vec::new();
+
+    // act
+    loop {
+        let mut builder = PredicateBuilder::new(None).limit(3);
+
+        if let Some(token) = token.as_ref() {
+            builder = builder.after(token);
+        }
+
+        let predicate = builder.build();
+        let page: Vec<_> = store
+            .load_raw(Some(&predicate))
+            .await
+            .try_collect()
+            .await
+            .box_err()?;
+
+        if let Some(last) = page.last() {
+            token = Some(ContinuationToken::from(last));
+        } else {
+            break;
+        }
+
+        amounts.extend(page.iter().map(|event| {
+            event.descriptor().to_json().unwrap()["amount"]
+                .as_f64()
+                .unwrap()
+        }));
+    }
+
+    // assert
+    amounts.sort_by(f64::total_cmp);
+    assert_eq!(amounts, vec![10.0, 20.0, 30.0, 40.0]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_raw_should_continue_at_next_identifier_after_missing_token() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::ordered(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+
+    for id in ["1", "3"] {
+        let mut account = Account::open(id);
+        account.credit(10.0);
+        repository.save(&mut account).await.box_err()?;
+    }
+
+    let token = ContinuationToken::new(String::from("2"), Version::from(1));
+    let predicate = PredicateBuilder::new(None).after(&token).build();
+
+    // act
+    let events: Vec<_> = store
+        .load_raw(Some(&predicate))
+        .await
+        .try_collect()
+        .await
+        .box_err()?;
+
+    // assert
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| event.id() == "3"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_raw_should_not_page_unordered_store() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::new(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+    let id = String::from("42");
+    let mut account = Account::open(id.clone());
+
+    account.credit(10.0);
+    repository.save(&mut account).await.box_err()?;
+
+    let predicate = PredicateBuilder::new(Some(&id)).limit(1).build();
+
+    // act
+    let result: Result<Vec<_>, _> = store.load_raw(Some(&predicate)).await.try_collect().await;
+
+    // assert
+    assert!(matches!(result, Err(event::StoreError::Unsupported)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn ids_page_should_resume_from_token() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::ordered(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+
+    for id in ["1", "2", "3"] {
+        let mut account = Account::open(id);
+        account.credit(10.0);
+        repository.save(&mut account).await.box_err()?;
+    }
+
+    let first = store
+        .ids_page(Default::default(), 2, None)
+        .await
+        .box_err()?;
+
+    // act
+    let next = store
+        .ids_page(Default::default(), 2, first.token())
+        .await
+        .box_err()?;
+
+    // assert
+    let mut ids: Vec<_> = first.ids().iter().chain(next.ids()).cloned().collect();
+
+    ids.sort();
+    assert_eq!(first.ids().len(), 2);
+    assert!(first.token().is_some());
+    assert_eq!(next.token(), None);
+    assert_eq!(ids, vec!["1", "2", "3"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn ids_page_should_continue_after_missing_identifier() -> TestResult {
+    // arrange
+    let options = StoreOptions::builder()
+        .clock(VirtualClock::new())
+        .transcoder(events())
+        .build();
+    let store = Arc::new(EventStore::<String>::ordered(options));
+    let repository: Repository<Account> = (store.clone() as Arc<dyn Store<String>>).into();
+
+    for id in ["1", "3", "4"] {
+        let mut account = Account::open(id);
+        account.credit(10.0);
+        repository.save(&mut account).await.box_err()?;
+    }
+
+    let after = String::from("2");
+
+    // act
+    let page = store
+        .ids_page(Default::default(), 10, Some(&after))
+        .await
+        .box_err()?;
+
+    // assert
+    assert_eq!(page.ids(), ["3", "4"]);
+    assert_eq!(page.token(), None);
+    Ok(())
+}
+
+#[test]
+fn decode_should_fail_for_unregistered_event_by_default() {
+    // arrange
+    let content = events().encode(&Credited::new("42", 50.0)).unwrap();
+    let options = StoreOptions::<String>::builder()
+        .transcoder(event::transcoder())
+        .build();
+
+    // act
+    let result = options.decode(&Credited::schema(), Version::default(), &content);
+
+    // assert
+    assert_eq!(
+        result.err().unwrap(),
+        EncodingError::Unregistered(Credited::schema())
+    );
+}
+
+#[test]
+fn decode_should_skip_unregistered_event() {
+    // arrange
+    let content = events().encode(&Credited::new("42", 50.0)).unwrap();
+    let options = StoreOptions::<String>::builder()
+        .transcoder(event::transcoder())
+        .decode_policy(DecodePolicy::Skip)
+        .build();
+
+    // act
+    let event = options
+        .decode(&Credited::schema(), Version::default(), &content)
+        .unwrap();
+
+    // assert
+    assert!(event.is_none());
+}
+
+#[test]
+fn decode_should_deliver_unregistered_event_as_unknown() {
+    // arrange
+    let content = events().encode(&Credited::new("42", 50.0)).unwrap();
+    let options = StoreOptions::<String>::builder()
+        .transcoder(event::transcoder())
+        .decode_policy(DecodePolicy::Deliver)
+        .build();
+
+    // act
+    let event = options
+        .decode(&Credited::schema(), Version::default(), &content)
+        .unwrap()
+        .unwrap();
+
+    // assert
+    let unknown = event.as_any().downcast_ref::<UnknownEvent>().unwrap();
+
+    assert_eq!(unknown.descriptor().schema, Credited::schema());
+    assert_eq!(unknown.descriptor().content, content);
+}
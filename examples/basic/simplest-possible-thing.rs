@@ -17,6 +17,9 @@ use std::error::Error;
 // - JSON ("json")
 // - CBOR ("cbor")
 // - Message Pack ("message-pack")
+// - Apache Avro ("avro")
+// - bincode ("bincode")
+// - postcard ("postcard")
 #[transcode(with = cqrs::encoding::ProtoBuf)]
 mod events {
     #[event]